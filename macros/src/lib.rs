@@ -0,0 +1,372 @@
+//! Procedural macros for the [`process-image`](https://docs.rs/process-image) crate.
+//!
+//! These macros translate IEC 61131-3 address strings like `"%IW16"` into the token form
+//! understood by the declarative macros of `process-image` (`W, 16`) and build the names of
+//! generated methods.  They are an implementation detail and should not be used directly.
+use proc_macro::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+
+/// Translate a single address string and pass it on to a macro.
+///
+/// ```text
+/// iec_tag!(path::to::callback (prefix...) "%IX1.2" suffix...)
+/// ```
+///
+/// expands to
+///
+/// ```text
+/// path::to::callback!(prefix..., X, 1, 2 suffix...)
+/// ```
+#[doc(hidden)]
+#[proc_macro]
+pub fn iec_tag(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+
+    let mut callback = Vec::new();
+    let prefix = loop {
+        match tokens.next() {
+            Some(TokenTree::Group(g)) if g.delimiter() == Delimiter::Parenthesis => {
+                break g.stream();
+            }
+            Some(tt) => callback.push(tt),
+            None => return error(Span::call_site(), "missing address in iec_tag!()"),
+        }
+    };
+
+    let address = match tokens.next().map(unwrap_fragment) {
+        Some(TokenTree::Literal(lit)) => lit,
+        Some(tt) => return error(tt.span(), EXPECTED_STRING),
+        None => return error(Span::call_site(), EXPECTED_STRING),
+    };
+    let tag = match translate(&address, None) {
+        Ok(tag) => tag,
+        Err(err) => return err,
+    };
+
+    let mut args = prefix;
+    args.extend([TokenTree::Punct(Punct::new(',', Spacing::Alone))]);
+    args.extend(tag);
+    args.extend(tokens);
+
+    invoke(callback, Delimiter::Parenthesis, args)
+}
+
+/// Translate all address strings of a tag table and pass it on to a macro.
+///
+/// Every string literal following a single `:` is replaced by the parenthesized tag, so
+///
+/// ```text
+/// iec_tags!(path::to::callback [I] { ... pub temp: "%ID12", ... })
+/// ```
+///
+/// expands to
+///
+/// ```text
+/// path::to::callback! { ... pub temp: (D, 12), ... }
+/// ```
+///
+/// The bracketed area of the process image is optional.  When it is given, all address strings
+/// must be in this area.
+#[doc(hidden)]
+#[proc_macro]
+pub fn iec_tags(input: TokenStream) -> TokenStream {
+    let mut callback = Vec::new();
+    let mut area = None;
+    for tt in input {
+        match tt {
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket => {
+                area = match parse_area(g.stream()) {
+                    Ok(area) => area,
+                    Err(err) => return err,
+                };
+            }
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                return match replace_addresses(g.stream(), area) {
+                    Ok(body) => invoke(callback, Delimiter::Brace, body),
+                    Err(err) => err,
+                };
+            }
+            tt => callback.push(tt),
+        }
+    }
+    error(Span::call_site(), "missing tag table in iec_tags!()")
+}
+
+/// Concatenate identifiers, e.g. for the names of generated methods.
+///
+/// Every `[< ... >]` group in the input is replaced by a single identifier made from the
+/// identifiers inside it, so
+///
+/// ```text
+/// paste_idents! { pub fn [<door_closed _rising>](&self) -> bool { ... } }
+/// ```
+///
+/// expands to
+///
+/// ```text
+/// pub fn door_closed_rising(&self) -> bool { ... }
+/// ```
+///
+/// The new identifier has the span of the first identifier in the group.
+#[doc(hidden)]
+#[proc_macro]
+pub fn paste_idents(input: TokenStream) -> TokenStream {
+    match paste(input) {
+        Ok(output) => output,
+        Err(err) => err,
+    }
+}
+
+fn paste(input: TokenStream) -> Result<TokenStream, TokenStream> {
+    let mut output = Vec::new();
+    for tt in input {
+        output.push(match tt {
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Bracket => match paste_group(&g)? {
+                Some(ident) => TokenTree::Ident(ident),
+                None => {
+                    let mut group = Group::new(Delimiter::Bracket, paste(g.stream())?);
+                    group.set_span(g.span());
+                    TokenTree::Group(group)
+                }
+            },
+            TokenTree::Group(g) => {
+                let mut group = Group::new(g.delimiter(), paste(g.stream())?);
+                group.set_span(g.span());
+                TokenTree::Group(group)
+            }
+            tt => tt,
+        });
+    }
+    Ok(output.into_iter().collect())
+}
+
+/// Concatenate the identifiers of a `[< ... >]` group, or `None` for other bracket groups.
+fn paste_group(group: &Group) -> Result<Option<Ident>, TokenStream> {
+    let tokens: Vec<_> = group.stream().into_iter().collect();
+    let inner = match tokens.as_slice() {
+        [TokenTree::Punct(open), inner @ .., TokenTree::Punct(close)]
+            if open.as_char() == '<' && close.as_char() == '>' =>
+        {
+            inner
+        }
+        _ => return Ok(None),
+    };
+
+    let mut name = String::new();
+    let mut span = None;
+    for tt in inner {
+        match unwrap_fragment(tt.clone()) {
+            TokenTree::Ident(ident) => {
+                name.push_str(&ident.to_string());
+                span.get_or_insert(ident.span());
+            }
+            tt => return Err(error(tt.span(), "expected an identifier in `[< ... >]`")),
+        }
+    }
+    match span {
+        Some(span) => Ok(Some(Ident::new(&name, span))),
+        None => Err(error(group.span(), "expected an identifier in `[< ... >]`")),
+    }
+}
+
+const EXPECTED_STRING: &str = "expected an IEC 61131-3 address string like \"%IW16\"";
+
+/// Parse the area marker of a process image, e.g. the `I` in `struct Inputs: 16 in I`.
+fn parse_area(input: TokenStream) -> Result<Option<char>, TokenStream> {
+    let mut tokens = input.into_iter();
+    match (tokens.next(), tokens.next()) {
+        (None, _) => Ok(None),
+        (Some(TokenTree::Ident(area)), None) => match area.to_string().as_str() {
+            "I" => Ok(Some('I')),
+            "Q" => Ok(Some('Q')),
+            "M" => Ok(Some('M')),
+            name => Err(error(
+                area.span(),
+                &format!("unknown process image area `{}`, expected I, Q, or M", name),
+            )),
+        },
+        (Some(tt), _) => Err(error(tt.span(), "expected a process image area I, Q, or M")),
+    }
+}
+
+fn replace_addresses(input: TokenStream, area: Option<char>) -> Result<TokenStream, TokenStream> {
+    let mut output = Vec::new();
+    let mut after_colon = false;
+    let mut previous_joint = false;
+
+    for tt in input {
+        let next = match unwrap_fragment(tt) {
+            TokenTree::Literal(lit) if after_colon && lit.to_string().starts_with('"') => {
+                let mut group = Group::new(Delimiter::Parenthesis, translate(&lit, area)?);
+                group.set_span(lit.span());
+                TokenTree::Group(group)
+            }
+            TokenTree::Group(g) if g.delimiter() == Delimiter::Brace => {
+                let mut group = Group::new(Delimiter::Brace, replace_addresses(g.stream(), area)?);
+                group.set_span(g.span());
+                TokenTree::Group(group)
+            }
+            tt => tt,
+        };
+
+        // Only a single `:` introduces a tag, `::` is part of a path.
+        let (colon, joint) = match &next {
+            TokenTree::Punct(p) if p.as_char() == ':' => (true, p.spacing() == Spacing::Joint),
+            _ => (false, false),
+        };
+        after_colon = colon && !joint && !previous_joint;
+        previous_joint = colon && joint;
+
+        output.push(next);
+    }
+
+    Ok(output.into_iter().collect())
+}
+
+/// Parse an address string literal into the tag tokens, e.g. `X, 1, 2`, `W, 16`, or `W, 16, 12`.
+///
+/// If `area` is given, the address must be in this area.
+fn translate(lit: &Literal, area: Option<char>) -> Result<TokenStream, TokenStream> {
+    let span = lit.span();
+    let repr = lit.to_string();
+    let address = repr
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .ok_or_else(|| error(span, EXPECTED_STRING))?;
+
+    let (address_area, width, byte, bit) = parse(address).map_err(|msg| {
+        error(
+            span,
+            &format!("invalid IEC 61131-3 address `{}`: {}", address, msg),
+        )
+    })?;
+    if let Some(area) = area.filter(|area| *area != address_area) {
+        return Err(error(
+            span,
+            &format!(
+                "address `{}` is not in the area `{}` of the process image",
+                address, area
+            ),
+        ));
+    }
+
+    let mut tokens = vec![TokenTree::Ident(Ident::new(width, span))];
+    tokens.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+    tokens.push(TokenTree::Literal(Literal::usize_unsuffixed(byte)));
+    if let Some(bit) = bit {
+        tokens.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+        tokens.push(TokenTree::Literal(Literal::u8_unsuffixed(bit)));
+    }
+    for tt in &mut tokens {
+        tt.set_span(span);
+    }
+    Ok(tokens.into_iter().collect())
+}
+
+/// Parse an IEC 61131-3 address into the area, width specifier, byte offset, and bit offset.
+///
+/// This follows the rules of `process_image::Address`.
+fn parse(address: &str) -> Result<(char, &'static str, usize, Option<u8>), &'static str> {
+    let s = address
+        .strip_prefix('%')
+        .ok_or("address must start with `%`")?;
+
+    let mut chars = s.chars();
+    let area = match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some(area @ ('I' | 'Q' | 'M')) => area,
+        _ => return Err("area must be one of I, Q, or M"),
+    };
+
+    let s = chars.as_str();
+    let mut chars = s.chars();
+    let (width, s) = match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some('X') => ("X", chars.as_str()),
+        Some('B') => ("B", chars.as_str()),
+        Some('W') => ("W", chars.as_str()),
+        Some('D') => ("D", chars.as_str()),
+        Some('L') => ("L", chars.as_str()),
+        // The `X` may be omitted for bit addresses
+        Some(c) if c.is_ascii_digit() => ("X", s),
+        _ => return Err("width must be one of X, B, W, D, or L"),
+    };
+
+    let (byte, bit) = match s.split_once('.') {
+        Some((byte, bit)) => (byte, Some(bit)),
+        None => (s, None),
+    };
+    let byte = parse_offset(byte).ok_or("invalid byte offset")?;
+
+    match (width, bit) {
+        ("X", Some(bit)) => match parse_offset(bit) {
+            Some(bit) if bit < 8 => Ok((area, width, byte, Some(bit as u8))),
+            _ => Err("bit offset must be in the range 0..=7"),
+        },
+        ("X", None) => Err("bit address is missing the bit offset"),
+        ("B", Some(_)) => Err("byte addresses cannot have a bit offset"),
+        (width, Some(bit)) => {
+            let bits = match width {
+                "W" => 16,
+                "D" => 32,
+                _ => 64,
+            };
+            match parse_offset(bit) {
+                Some(bit) if bit < bits => Ok((area, width, byte, Some(bit as u8))),
+                _ => Err("bit offset exceeds the addressed word"),
+            }
+        }
+        (width, None) => Ok((area, width, byte, None)),
+    }
+}
+
+/// Parse a decimal offset, rejecting signs and empty strings.
+fn parse_offset(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Unwrap a `$x:literal` or `$x:ident` fragment, which may be forwarded as an invisible group.
+fn unwrap_fragment(tt: TokenTree) -> TokenTree {
+    if let TokenTree::Group(g) = &tt {
+        if g.delimiter() == Delimiter::None {
+            let mut inner = g.stream().into_iter();
+            if let (Some(tt @ (TokenTree::Literal(_) | TokenTree::Ident(_))), None) =
+                (inner.next(), inner.next())
+            {
+                return tt;
+            }
+        }
+    }
+    tt
+}
+
+/// Build `callback!(args)` with the given delimiter.
+fn invoke(callback: Vec<TokenTree>, delimiter: Delimiter, args: TokenStream) -> TokenStream {
+    let mut output: TokenStream = callback.into_iter().collect();
+    output.extend([
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(delimiter, args)),
+    ]);
+    output
+}
+
+/// Build `::core::compile_error! { "msg" }` pointing at `span`.
+fn error(span: Span, msg: &str) -> TokenStream {
+    let mut tokens = vec![
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("core", span)),
+        TokenTree::Punct(Punct::new(':', Spacing::Joint)),
+        TokenTree::Punct(Punct::new(':', Spacing::Alone)),
+        TokenTree::Ident(Ident::new("compile_error", span)),
+        TokenTree::Punct(Punct::new('!', Spacing::Alone)),
+        TokenTree::Group(Group::new(
+            Delimiter::Brace,
+            TokenTree::Literal(Literal::string(msg)).into(),
+        )),
+    ];
+    for tt in &mut tokens {
+        tt.set_span(span);
+    }
+    tokens.into_iter().collect()
+}
@@ -1,6 +1,10 @@
+use core::marker::PhantomData;
 use core::ops::Deref;
 use core::ops::DerefMut;
 
+use crate::byte_order::{BigEndian, ByteOrder};
+use crate::value::Value;
+
 /// Mutable accessor for a single bit.
 ///
 /// This type dereferences to an `&mut bool` which can be used to write the value of a single bit
@@ -46,23 +50,35 @@ impl Drop for BitMut<'_> {
 
 /// Mutable accessor for a word.
 ///
-/// This type dereferences to an `&mut u16` which can be used to write the value of a word in the
-/// process image.
+/// This type dereferences to an `&mut u16` which can be used to write the value of a word
+/// in the process image.  The byte order is selected by the type parameter `O`.
 #[derive(Debug)]
-pub struct WordMut<'a> {
+pub struct WordMut<'a, O: ByteOrder = BigEndian> {
     buf: &'a mut [u8; 2],
     value: u16,
+    order: PhantomData<O>,
 }
 
 impl<'a> WordMut<'a> {
     #[inline(always)]
     pub fn new(buf: &'a mut [u8; 2]) -> Self {
-        let value = u16::from_be_bytes(*buf);
-        Self { buf, value }
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> WordMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 2]) -> Self {
+        let value = O::read_u16(*buf);
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
     }
 }
 
-impl Deref for WordMut<'_> {
+impl<O: ByteOrder> Deref for WordMut<'_, O> {
     type Target = u16;
 
     #[inline(always)]
@@ -71,39 +87,51 @@ impl Deref for WordMut<'_> {
     }
 }
 
-impl DerefMut for WordMut<'_> {
+impl<O: ByteOrder> DerefMut for WordMut<'_, O> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.value
     }
 }
 
-impl Drop for WordMut<'_> {
+impl<O: ByteOrder> Drop for WordMut<'_, O> {
     #[inline(always)]
     fn drop(&mut self) {
-        *self.buf = self.value.to_be_bytes();
+        *self.buf = O::write_u16(self.value);
     }
 }
 
 /// Mutable accessor for a double word.
 ///
 /// This type dereferences to an `&mut u32` which can be used to write the value of a double word
-/// in the process image.
+/// in the process image.  The byte order is selected by the type parameter `O`.
 #[derive(Debug)]
-pub struct DWordMut<'a> {
+pub struct DWordMut<'a, O: ByteOrder = BigEndian> {
     buf: &'a mut [u8; 4],
     value: u32,
+    order: PhantomData<O>,
 }
 
 impl<'a> DWordMut<'a> {
     #[inline(always)]
     pub fn new(buf: &'a mut [u8; 4]) -> Self {
-        let value = u32::from_be_bytes(*buf);
-        Self { buf, value }
+        Self::with_byte_order(buf)
     }
 }
 
-impl Deref for DWordMut<'_> {
+impl<'a, O: ByteOrder> DWordMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 4]) -> Self {
+        let value = O::read_u32(*buf);
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for DWordMut<'_, O> {
     type Target = u32;
 
     #[inline(always)]
@@ -112,39 +140,51 @@ impl Deref for DWordMut<'_> {
     }
 }
 
-impl DerefMut for DWordMut<'_> {
+impl<O: ByteOrder> DerefMut for DWordMut<'_, O> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.value
     }
 }
 
-impl Drop for DWordMut<'_> {
+impl<O: ByteOrder> Drop for DWordMut<'_, O> {
     #[inline(always)]
     fn drop(&mut self) {
-        *self.buf = self.value.to_be_bytes();
+        *self.buf = O::write_u32(self.value);
     }
 }
 
 /// Mutable accessor for a long word.
 ///
-/// This type dereferences to an `&mut u64` which can be used to write the value of a long word in
-/// the process image.
+/// This type dereferences to an `&mut u64` which can be used to write the value of a long word
+/// in the process image.  The byte order is selected by the type parameter `O`.
 #[derive(Debug)]
-pub struct LWordMut<'a> {
+pub struct LWordMut<'a, O: ByteOrder = BigEndian> {
     buf: &'a mut [u8; 8],
     value: u64,
+    order: PhantomData<O>,
 }
 
 impl<'a> LWordMut<'a> {
     #[inline(always)]
     pub fn new(buf: &'a mut [u8; 8]) -> Self {
-        let value = u64::from_be_bytes(*buf);
-        Self { buf, value }
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> LWordMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 8]) -> Self {
+        let value = O::read_u64(*buf);
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
     }
 }
 
-impl Deref for LWordMut<'_> {
+impl<O: ByteOrder> Deref for LWordMut<'_, O> {
     type Target = u64;
 
     #[inline(always)]
@@ -153,16 +193,488 @@ impl Deref for LWordMut<'_> {
     }
 }
 
-impl DerefMut for LWordMut<'_> {
+impl<O: ByteOrder> DerefMut for LWordMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for LWordMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u64(self.value);
+    }
+}
+
+/// Mutable accessor for a short integer.
+///
+/// This type dereferences to an `&mut i8` which can be used to write the value of a short integer
+/// in the process image.
+#[derive(Debug)]
+pub struct SIntMut<'a> {
+    buf: &'a mut u8,
+    value: i8,
+}
+
+impl<'a> SIntMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut u8) -> Self {
+        let value = *buf as i8;
+        Self { buf, value }
+    }
+}
+
+impl Deref for SIntMut<'_> {
+    type Target = i8;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for SIntMut<'_> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl Drop for SIntMut<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = self.value as u8;
+    }
+}
+
+/// Mutable accessor for an integer.
+///
+/// This type dereferences to an `&mut i16` which can be used to write the value of an integer
+/// in the process image.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct IntMut<'a, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8; 2],
+    value: i16,
+    order: PhantomData<O>,
+}
+
+impl<'a> IntMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8; 2]) -> Self {
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> IntMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 2]) -> Self {
+        let value = O::read_u16(*buf) as i16;
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for IntMut<'_, O> {
+    type Target = i16;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<O: ByteOrder> DerefMut for IntMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for IntMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u16(self.value as u16);
+    }
+}
+
+/// Mutable accessor for a double integer.
+///
+/// This type dereferences to an `&mut i32` which can be used to write the value of a double integer
+/// in the process image.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct DIntMut<'a, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8; 4],
+    value: i32,
+    order: PhantomData<O>,
+}
+
+impl<'a> DIntMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8; 4]) -> Self {
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> DIntMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 4]) -> Self {
+        let value = O::read_u32(*buf) as i32;
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for DIntMut<'_, O> {
+    type Target = i32;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<O: ByteOrder> DerefMut for DIntMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for DIntMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u32(self.value as u32);
+    }
+}
+
+/// Mutable accessor for a long integer.
+///
+/// This type dereferences to an `&mut i64` which can be used to write the value of a long integer
+/// in the process image.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct LIntMut<'a, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8; 8],
+    value: i64,
+    order: PhantomData<O>,
+}
+
+impl<'a> LIntMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8; 8]) -> Self {
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> LIntMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 8]) -> Self {
+        let value = O::read_u64(*buf) as i64;
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for LIntMut<'_, O> {
+    type Target = i64;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<O: ByteOrder> DerefMut for LIntMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for LIntMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u64(self.value as u64);
+    }
+}
+
+/// Mutable accessor for a real.
+///
+/// This type dereferences to an `&mut f32` which can be used to write the value of a real
+/// in the process image.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct RealMut<'a, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8; 4],
+    value: f32,
+    order: PhantomData<O>,
+}
+
+impl<'a> RealMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8; 4]) -> Self {
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> RealMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 4]) -> Self {
+        let value = f32::from_bits(O::read_u32(*buf));
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for RealMut<'_, O> {
+    type Target = f32;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<O: ByteOrder> DerefMut for RealMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for RealMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u32(self.value.to_bits());
+    }
+}
+
+/// Mutable accessor for a long real.
+///
+/// This type dereferences to an `&mut f64` which can be used to write the value of a long real
+/// in the process image.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct LRealMut<'a, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8; 8],
+    value: f64,
+    order: PhantomData<O>,
+}
+
+impl<'a> LRealMut<'a> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8; 8]) -> Self {
+        Self::with_byte_order(buf)
+    }
+}
+
+impl<'a, O: ByteOrder> LRealMut<'a, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8; 8]) -> Self {
+        let value = f64::from_bits(O::read_u64(*buf));
+        Self {
+            buf,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<O: ByteOrder> Deref for LRealMut<'_, O> {
+    type Target = f64;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<O: ByteOrder> DerefMut for LRealMut<'_, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<O: ByteOrder> Drop for LRealMut<'_, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        *self.buf = O::write_u64(self.value.to_bits());
+    }
+}
+
+/// Unsigned integer type of a bit field tag.
+///
+/// Bit fields are tags like `(W, 0, 4..=6)` which only contain some of the bits of a byte, word,
+/// double word, or long word.  Their value is the integer formed by these bits.
+pub trait BitField: Copy {
+    /// Extract the bits `low..=high`, shifted down to bit 0.
+    ///
+    /// # Panics
+    /// Panics unless `low <= high` and `high` is less than the number of bits of `Self`.
+    fn bits(self, low: u8, high: u8) -> Self;
+
+    /// Replace the bits `low..=high` with the lowest bits of `value`.
+    ///
+    /// # Panics
+    /// Panics unless `low <= high` and `high` is less than the number of bits of `Self`.
+    fn with_bits(self, low: u8, high: u8, value: Self) -> Self;
+
+    #[doc(hidden)]
+    fn read<O: ByteOrder>(bytes: &[u8]) -> Self;
+
+    #[doc(hidden)]
+    fn write<O: ByteOrder>(self, bytes: &mut [u8]);
+
+    #[doc(hidden)]
+    fn from_value(value: Value) -> Option<Self>;
+
+    /// Byte and bit in this byte which hold the bit `index` of the value in byte order `O`.
+    #[doc(hidden)]
+    fn bit_position<O: ByteOrder>(index: u8) -> (usize, u8);
+}
+
+#[inline(always)]
+fn bit_range_assert(low: u8, high: u8, bits: u32) {
+    assert!(
+        low <= high && u32::from(high) < bits,
+        "Bit field must be an ascending range within the width of the tag",
+    );
+}
+
+macro_rules! bit_field {
+    ($($int:ident: $variant:ident, |$bytes:ident| $read:expr, |$value:ident| $write:expr;)*) => {
+        $(
+            impl BitField for $int {
+                #[inline(always)]
+                fn bits(self, low: u8, high: u8) -> Self {
+                    bit_range_assert(low, high, $int::BITS);
+                    let mask = $int::MAX >> ($int::BITS - u32::from(high - low + 1));
+                    (self >> low) & mask
+                }
+
+                #[inline(always)]
+                fn with_bits(self, low: u8, high: u8, value: Self) -> Self {
+                    bit_range_assert(low, high, $int::BITS);
+                    let mask = $int::MAX >> ($int::BITS - u32::from(high - low + 1));
+                    (self & !(mask << low)) | ((value & mask) << low)
+                }
+
+                #[inline(always)]
+                fn read<O: ByteOrder>($bytes: &[u8]) -> Self {
+                    $read
+                }
+
+                #[inline(always)]
+                fn write<O: ByteOrder>(self, $bytes: &mut [u8]) {
+                    let $value = self;
+                    $write
+                }
+
+                #[inline(always)]
+                fn from_value(value: Value) -> Option<Self> {
+                    match value {
+                        Value::$variant(v) => Some(v),
+                        _ => None,
+                    }
+                }
+
+                #[inline(always)]
+                fn bit_position<O: ByteOrder>(index: u8) -> (usize, u8) {
+                    assert!(
+                        u32::from(index) < $int::BITS,
+                        "Bit index exceeds the width of the tag",
+                    );
+                    let mut bytes = [0x00; 8];
+                    let value: $int = 1 << index;
+                    value.write::<O>(&mut bytes);
+                    let byte = bytes.iter().position(|b| *b != 0).unwrap();
+                    (byte, bytes[byte].trailing_zeros() as u8)
+                }
+            }
+        )*
+    };
+}
+
+bit_field! {
+    u8: Byte, |bytes| bytes[0], |value| bytes[0] = value;
+    u16: Word,
+        |bytes| O::read_u16(bytes[..2].try_into().unwrap()),
+        |value| bytes[..2].copy_from_slice(&O::write_u16(value));
+    u32: DWord,
+        |bytes| O::read_u32(bytes[..4].try_into().unwrap()),
+        |value| bytes[..4].copy_from_slice(&O::write_u32(value));
+    u64: LWord,
+        |bytes| O::read_u64(bytes[..8].try_into().unwrap()),
+        |value| bytes[..8].copy_from_slice(&O::write_u64(value));
+}
+
+/// Mutable accessor for a bit field.
+///
+/// This type dereferences to an `&mut T` which can be used to write the value of a bit field in
+/// the process image.  Only the bits of the field are written back, the other bits of the byte or
+/// word stay untouched and bits of the value beyond the width of the field are ignored.  The byte
+/// order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct BitFieldMut<'a, T: BitField, O: ByteOrder = BigEndian> {
+    buf: &'a mut [u8],
+    low: u8,
+    high: u8,
+    value: T,
+    order: PhantomData<O>,
+}
+
+impl<'a, T: BitField> BitFieldMut<'a, T> {
+    #[inline(always)]
+    pub fn new(buf: &'a mut [u8], low: u8, high: u8) -> Self {
+        Self::with_byte_order(buf, low, high)
+    }
+}
+
+impl<'a, T: BitField, O: ByteOrder> BitFieldMut<'a, T, O> {
+    #[inline(always)]
+    pub fn with_byte_order(buf: &'a mut [u8], low: u8, high: u8) -> Self {
+        let value = T::read::<O>(buf).bits(low, high);
+        Self {
+            buf,
+            low,
+            high,
+            value,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: BitField, O: ByteOrder> Deref for BitFieldMut<'_, T, O> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: BitField, O: ByteOrder> DerefMut for BitFieldMut<'_, T, O> {
     #[inline(always)]
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.value
     }
 }
 
-impl Drop for LWordMut<'_> {
+impl<T: BitField, O: ByteOrder> Drop for BitFieldMut<'_, T, O> {
     #[inline(always)]
     fn drop(&mut self) {
-        *self.buf = self.value.to_be_bytes();
+        let word = T::read::<O>(self.buf).with_bits(self.low, self.high, self.value);
+        word.write::<O>(self.buf);
     }
 }
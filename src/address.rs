@@ -0,0 +1,622 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::access::BitField;
+use crate::byte_order::{BigEndian, ByteOrder};
+use crate::value::Value;
+
+/// The process image an [`Address`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Area {
+    /// Process image of inputs (`I`)
+    Input,
+    /// Process image of outputs (`Q`)
+    Output,
+    /// Internal memory (`M`)
+    Memory,
+}
+
+impl Area {
+    /// The IEC 61131-3 letter of this area.
+    pub const fn letter(self) -> char {
+        match self {
+            Area::Input => 'I',
+            Area::Output => 'Q',
+            Area::Memory => 'M',
+        }
+    }
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// The data width an [`Address`] refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Width {
+    /// Boolean Bit (`X`)
+    Bit,
+    /// Byte (`B`)
+    Byte,
+    /// Word (`W`)
+    Word,
+    /// Double Word (`D`)
+    DWord,
+    /// Long Word (`L`)
+    LWord,
+}
+
+impl Width {
+    /// The IEC 61131-3 letter of this width.
+    pub const fn letter(self) -> char {
+        match self {
+            Width::Bit => 'X',
+            Width::Byte => 'B',
+            Width::Word => 'W',
+            Width::DWord => 'D',
+            Width::LWord => 'L',
+        }
+    }
+
+    /// Number of bytes occupied in the process image.
+    pub const fn size(self) -> usize {
+        match self {
+            Width::Bit | Width::Byte => 1,
+            Width::Word => 2,
+            Width::DWord => 4,
+            Width::LWord => 8,
+        }
+    }
+}
+
+impl fmt::Display for Width {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.letter())
+    }
+}
+
+/// An IEC 61131-3 address like `%IX100.4`, `%QW16`, or `%MW10.12`.
+///
+/// Addresses can be parsed from and formatted to their textual representation:
+///
+/// ```
+/// use process_image::{Address, Area, Width};
+///
+/// let addr: Address = "%IX100.4".parse().unwrap();
+/// assert_eq!(addr.area(), Area::Input);
+/// assert_eq!(addr.width(), Width::Bit);
+/// assert_eq!(addr.byte_offset(), 100);
+/// assert_eq!(addr.bit_offset(), Some(4));
+///
+/// let addr = Address::new(Area::Output, Width::Word, 16);
+/// assert_eq!(addr.to_string(), "%QW16");
+/// ```
+///
+/// They can then be used to access values in a process image buffer at runtime:
+///
+/// ```
+/// use process_image::{Address, Value};
+///
+/// let mut buf = [0x00; 8];
+/// let addr: Address = "%MW2".parse().unwrap();
+///
+/// addr.write(&mut buf, Value::Word(1337)).unwrap();
+/// assert_eq!(addr.read(&buf), Ok(Value::Word(1337)));
+/// assert_eq!(process_image::tag!(&buf, W, 2), 1337);
+/// ```
+///
+/// Bits can also be addressed inside a word, double word, or long word.  The bit offset then
+/// counts from the least significant bit of the whole value, so which byte holds the bit depends
+/// on the byte order.  For `%MW10.12` in big-endian order, this is bit 4 of byte 10.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Address {
+    area: Area,
+    width: Width,
+    byte: usize,
+    bit: Option<u8>,
+}
+
+impl Address {
+    /// Address of a byte, word, double word, or long word.
+    ///
+    /// # Panics
+    /// Panics when `width` is [`Width::Bit`].  Use [`Address::new_bit()`] for bit addresses.
+    pub const fn new(area: Area, width: Width, byte: usize) -> Self {
+        assert!(
+            !matches!(width, Width::Bit),
+            "Bit addresses need a bit offset"
+        );
+        Self {
+            area,
+            width,
+            byte,
+            bit: None,
+        }
+    }
+
+    /// Address of a single bit.
+    ///
+    /// # Panics
+    /// Panics when `bit` is not in the range `0..=7`.
+    pub const fn new_bit(area: Area, byte: usize, bit: u8) -> Self {
+        assert!(bit < 8, "Bit offset must be in the range 0..=7");
+        Self {
+            area,
+            width: Width::Bit,
+            byte,
+            bit: Some(bit),
+        }
+    }
+
+    /// Address of a single bit in a word, double word, or long word, like `%MW10.12`.
+    ///
+    /// # Panics
+    /// Panics when `width` is not [`Width::Word`], [`Width::DWord`], or [`Width::LWord`], or when
+    /// `bit` exceeds the width.
+    pub const fn new_bit_in(area: Area, width: Width, byte: usize, bit: u8) -> Self {
+        assert!(
+            matches!(width, Width::Word | Width::DWord | Width::LWord),
+            "Only bits of words, double words, and long words can be addressed"
+        );
+        assert!(
+            (bit as usize) < width.size() * 8,
+            "Bit offset exceeds the width of the address"
+        );
+        Self {
+            area,
+            width,
+            byte,
+            bit: Some(bit),
+        }
+    }
+
+    /// The process image this address refers to.
+    pub const fn area(&self) -> Area {
+        self.area
+    }
+
+    /// The data width this address refers to.
+    pub const fn width(&self) -> Width {
+        self.width
+    }
+
+    /// Offset of the (first) addressed byte in the process image.
+    pub const fn byte_offset(&self) -> usize {
+        self.byte
+    }
+
+    /// Offset of the addressed bit in its byte or word, for bit addresses.
+    pub const fn bit_offset(&self) -> Option<u8> {
+        self.bit
+    }
+
+    /// Byte relative to the byte offset and bit in this byte which hold the addressed bit.
+    fn bit_position<O: ByteOrder>(&self) -> Option<(usize, u8)> {
+        let bit = self.bit?;
+        Some(match self.width {
+            Width::Bit | Width::Byte => (0, bit),
+            Width::Word => u16::bit_position::<O>(bit),
+            Width::DWord => u32::bit_position::<O>(bit),
+            Width::LWord => u64::bit_position::<O>(bit),
+        })
+    }
+
+    /// Read the addressed value from a process image buffer in big-endian byte order.
+    pub fn read(&self, buf: &[u8]) -> Result<Value, AccessError> {
+        self.read_with_byte_order::<BigEndian>(buf)
+    }
+
+    /// Read the addressed value from a process image buffer in the given byte order.
+    pub fn read_with_byte_order<O: ByteOrder>(&self, buf: &[u8]) -> Result<Value, AccessError> {
+        let end = self
+            .byte
+            .checked_add(self.width.size())
+            .ok_or(AccessError::OutOfBounds)?;
+        let bytes = buf.get(self.byte..end).ok_or(AccessError::OutOfBounds)?;
+        if let Some((byte, bit)) = self.bit_position::<O>() {
+            return Ok(Value::Bool(bytes[byte] & (1 << bit) != 0));
+        }
+        Ok(match self.width {
+            Width::Bit => unreachable!("bit addresses always have a bit offset"),
+            Width::Byte => Value::Byte(bytes[0]),
+            Width::Word => Value::Word(O::read_u16(bytes.try_into().unwrap())),
+            Width::DWord => Value::DWord(O::read_u32(bytes.try_into().unwrap())),
+            Width::LWord => Value::LWord(O::read_u64(bytes.try_into().unwrap())),
+        })
+    }
+
+    /// Write a value to the addressed location in a process image buffer in big-endian byte order.
+    pub fn write(&self, buf: &mut [u8], value: Value) -> Result<(), AccessError> {
+        self.write_with_byte_order::<BigEndian>(buf, value)
+    }
+
+    /// Write a value to the addressed location in a process image buffer in the given byte order.
+    ///
+    /// The value must match the width of the address.  Signed integer and floating point values
+    /// can be written to addresses of the same size, e.g. [`Value::Real`] to a double word.
+    pub fn write_with_byte_order<O: ByteOrder>(
+        &self,
+        buf: &mut [u8],
+        value: Value,
+    ) -> Result<(), AccessError> {
+        let end = self
+            .byte
+            .checked_add(self.width.size())
+            .ok_or(AccessError::OutOfBounds)?;
+        let bytes = buf
+            .get_mut(self.byte..end)
+            .ok_or(AccessError::OutOfBounds)?;
+        if let Some((byte, bit)) = self.bit_position::<O>() {
+            let Value::Bool(v) = value else {
+                return Err(AccessError::TypeMismatch);
+            };
+            bytes[byte] &= !(1 << bit);
+            bytes[byte] |= u8::from(v) << bit;
+            return Ok(());
+        }
+        match (self.width, value) {
+            (Width::Byte, Value::Byte(v)) => bytes[0] = v,
+            (Width::Word, Value::Word(v)) => bytes.copy_from_slice(&O::write_u16(v)),
+            (Width::DWord, Value::DWord(v)) => bytes.copy_from_slice(&O::write_u32(v)),
+            (Width::LWord, Value::LWord(v)) => bytes.copy_from_slice(&O::write_u64(v)),
+            (Width::Byte, Value::SInt(v)) => bytes[0] = v as u8,
+            (Width::Word, Value::Int(v)) => bytes.copy_from_slice(&O::write_u16(v as u16)),
+            (Width::DWord, Value::DInt(v)) => bytes.copy_from_slice(&O::write_u32(v as u32)),
+            (Width::DWord, Value::Real(v)) => bytes.copy_from_slice(&O::write_u32(v.to_bits())),
+            (Width::LWord, Value::LInt(v)) => bytes.copy_from_slice(&O::write_u64(v as u64)),
+            (Width::LWord, Value::LReal(v)) => bytes.copy_from_slice(&O::write_u64(v.to_bits())),
+            _ => return Err(AccessError::TypeMismatch),
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "%{}{}{}", self.area, self.width, self.byte)?;
+        if let Some(bit) = self.bit_offset() {
+            write!(f, ".{}", bit)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Address {
+    type Err = ParseAddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s
+            .strip_prefix('%')
+            .ok_or(ParseAddressError::MissingPrefix)?;
+
+        let mut chars = s.chars();
+        let area = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('I') => Area::Input,
+            Some('Q') => Area::Output,
+            Some('M') => Area::Memory,
+            _ => return Err(ParseAddressError::InvalidArea),
+        };
+
+        let s = chars.as_str();
+        let mut chars = s.chars();
+        let (width, s) = match chars.next().map(|c| c.to_ascii_uppercase()) {
+            Some('X') => (Width::Bit, chars.as_str()),
+            Some('B') => (Width::Byte, chars.as_str()),
+            Some('W') => (Width::Word, chars.as_str()),
+            Some('D') => (Width::DWord, chars.as_str()),
+            Some('L') => (Width::LWord, chars.as_str()),
+            // The `X` may be omitted for bit addresses
+            Some(c) if c.is_ascii_digit() => (Width::Bit, s),
+            _ => return Err(ParseAddressError::InvalidWidth),
+        };
+
+        let (byte, bit) = match s.split_once('.') {
+            Some((byte, bit)) => (byte, Some(bit)),
+            None => (s, None),
+        };
+        let byte = parse_offset(byte).ok_or(ParseAddressError::InvalidByteOffset)?;
+
+        match (width, bit) {
+            (Width::Bit, Some(bit)) => match parse_offset(bit) {
+                Some(bit) if bit < 8 => Ok(Address::new_bit(area, byte, bit as u8)),
+                _ => Err(ParseAddressError::InvalidBitOffset),
+            },
+            (Width::Bit, None) => Err(ParseAddressError::MissingBitOffset),
+            (Width::Byte, Some(_)) => Err(ParseAddressError::UnexpectedBitOffset),
+            (width, Some(bit)) => match parse_offset(bit) {
+                Some(bit) if bit < width.size() * 8 => {
+                    Ok(Address::new_bit_in(area, width, byte, bit as u8))
+                }
+                _ => Err(ParseAddressError::InvalidBitOffset),
+            },
+            (width, None) => Ok(Address::new(area, width, byte)),
+        }
+    }
+}
+
+/// Parse a decimal offset, rejecting signs and empty strings.
+fn parse_offset(s: &str) -> Option<usize> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    s.parse().ok()
+}
+
+/// Error when parsing an [`Address`] from a string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseAddressError {
+    /// The address does not start with `%`.
+    MissingPrefix,
+    /// The area is not one of `I`, `Q`, or `M`.
+    InvalidArea,
+    /// The width is not one of `X`, `B`, `W`, `D`, or `L`.
+    InvalidWidth,
+    /// The byte offset is missing or not a number.
+    InvalidByteOffset,
+    /// The bit offset is not a number within the addressed byte or word.
+    InvalidBitOffset,
+    /// A bit address is missing its bit offset.
+    MissingBitOffset,
+    /// A bit offset was given for a byte address.
+    UnexpectedBitOffset,
+}
+
+impl fmt::Display for ParseAddressError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParseAddressError::MissingPrefix => "address must start with `%`",
+            ParseAddressError::InvalidArea => "area must be one of I, Q, or M",
+            ParseAddressError::InvalidWidth => "width must be one of X, B, W, D, or L",
+            ParseAddressError::InvalidByteOffset => "invalid byte offset",
+            ParseAddressError::InvalidBitOffset => "bit offset exceeds the addressed byte or word",
+            ParseAddressError::MissingBitOffset => "bit address is missing the bit offset",
+            ParseAddressError::UnexpectedBitOffset => "byte addresses cannot have a bit offset",
+        })
+    }
+}
+
+impl core::error::Error for ParseAddressError {}
+
+/// Error when accessing a process image buffer at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessError {
+    /// The addressed data lies outside of the buffer.
+    OutOfBounds,
+    /// The value does not match the width of the address.
+    TypeMismatch,
+    /// The tag is not in the [`TagTable`][`crate::TagTable`].
+    UnknownTag,
+}
+
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            AccessError::OutOfBounds => "address is outside of the process image",
+            AccessError::TypeMismatch => "value does not match the width of the address",
+            AccessError::UnknownTag => "unknown tag",
+        })
+    }
+}
+
+impl core::error::Error for AccessError {}
+
+/// Debug representation of a tag in a process image, e.g. `%IX0.1 = true`.
+///
+/// The bits of bit field tags are appended to the address, e.g. `%MW0.8..=10 = 5`.
+#[doc(hidden)]
+pub struct DebugTag<T> {
+    address: Address,
+    bit_field: Option<(u8, u8)>,
+    value: T,
+}
+
+impl<T> DebugTag<T> {
+    #[inline(always)]
+    pub const fn new(address: Address, bit_field: Option<(u8, u8)>, value: T) -> Self {
+        Self {
+            address,
+            bit_field,
+            value,
+        }
+    }
+
+    #[inline(always)]
+    pub const fn of_tag(tag: &crate::TagInfo, value: T) -> Self {
+        Self::new(tag.address(), tag.bit_field, value)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for DebugTag<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.address)?;
+        if let Some((low, high)) = self.bit_field {
+            write!(f, ".{}..={}", low, high)?;
+        }
+        f.write_str(" = ")?;
+        self.value.fmt(f)
+    }
+}
+
+/// Map the area marker of a process image to [`Area`].  Without a marker, the image is memory.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! area {
+    ([]) => {
+        $crate::Area::Memory
+    };
+    ([I]) => {
+        $crate::Area::Input
+    };
+    ([Q]) => {
+        $crate::Area::Output
+    };
+    ([M]) => {
+        $crate::Area::Memory
+    };
+}
+
+/// Build the [`Address`] of a tag in a process image.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_address {
+    ($area:expr, $kind:ident @ $order:ident, $($addr:tt)+) => {
+        $crate::tag_address!($area, $kind, $($addr)+)
+    };
+    ($area:expr, $kind:ident, $addr:literal, $low:literal ..= $high:literal) => {
+        $crate::tag_address!($area, $kind, $addr)
+    };
+    ($area:expr, X, $addr1:literal, $addr2:literal) => {
+        $crate::Address::new_bit($area, $addr1, $addr2)
+    };
+    ($area:expr, W, $addr:literal, $bit:literal) => {
+        $crate::Address::new_bit_in($area, $crate::Width::Word, $addr, $bit)
+    };
+    ($area:expr, D, $addr:literal, $bit:literal) => {
+        $crate::Address::new_bit_in($area, $crate::Width::DWord, $addr, $bit)
+    };
+    ($area:expr, L, $addr:literal, $bit:literal) => {
+        $crate::Address::new_bit_in($area, $crate::Width::LWord, $addr, $bit)
+    };
+    ($area:expr, B, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::Byte, $addr)
+    };
+    ($area:expr, SINT, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::Byte, $addr)
+    };
+    ($area:expr, W, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::Word, $addr)
+    };
+    ($area:expr, INT, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::Word, $addr)
+    };
+    ($area:expr, D, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::DWord, $addr)
+    };
+    ($area:expr, DINT, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::DWord, $addr)
+    };
+    ($area:expr, REAL, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::DWord, $addr)
+    };
+    ($area:expr, L, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::LWord, $addr)
+    };
+    ($area:expr, LINT, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::LWord, $addr)
+    };
+    ($area:expr, LREAL, $addr:literal) => {
+        $crate::Address::new($area, $crate::Width::LWord, $addr)
+    };
+    ($area:expr, $addr1:literal, $addr2:literal) => {
+        $crate::Address::new_bit($area, $addr1, $addr2)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_display() {
+        for s in [
+            "%IX100.4", "%QB8", "%MW16", "%ID4", "%QL8", "%MX0.0", "%MW10.12",
+        ] {
+            let addr: Address = s.parse().unwrap();
+            assert_eq!(addr.to_string(), s);
+        }
+
+        assert_eq!("%I0.7".parse(), Ok(Address::new_bit(Area::Input, 0, 7)));
+        assert_eq!(
+            "%qd12".parse(),
+            Ok(Address::new(Area::Output, Width::DWord, 12))
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let err = |s: &str| s.parse::<Address>().unwrap_err();
+
+        assert_eq!(err("IX0.0"), ParseAddressError::MissingPrefix);
+        assert_eq!(err("%"), ParseAddressError::InvalidArea);
+        assert_eq!(err("%AX0.0"), ParseAddressError::InvalidArea);
+        assert_eq!(err("%I"), ParseAddressError::InvalidWidth);
+        assert_eq!(err("%IY0"), ParseAddressError::InvalidWidth);
+        assert_eq!(err("%IW"), ParseAddressError::InvalidByteOffset);
+        assert_eq!(err("%IW+2"), ParseAddressError::InvalidByteOffset);
+        assert_eq!(err("%IX0.8"), ParseAddressError::InvalidBitOffset);
+        assert_eq!(err("%IX0."), ParseAddressError::InvalidBitOffset);
+        assert_eq!(err("%IX3"), ParseAddressError::MissingBitOffset);
+        assert_eq!(err("%IB2.1"), ParseAddressError::UnexpectedBitOffset);
+        assert_eq!(err("%IW2.16"), ParseAddressError::InvalidBitOffset);
+    }
+
+    #[test]
+    fn read_write() {
+        let mut buf = [0x00; 8];
+
+        let bit: Address = "%MX1.3".parse().unwrap();
+        bit.write(&mut buf, Value::Bool(true)).unwrap();
+        assert_eq!(buf[1], 0x08);
+        assert_eq!(bit.read(&buf), Ok(Value::Bool(true)));
+
+        let word: Address = "%MW2".parse().unwrap();
+        word.write_with_byte_order::<crate::LittleEndian>(&mut buf, Value::Word(0x1234))
+            .unwrap();
+        assert_eq!(buf[2..4], [0x34, 0x12]);
+        assert_eq!(word.read(&buf), Ok(Value::Word(0x3412)));
+
+        let lword: Address = "%ML4".parse().unwrap();
+        assert_eq!(lword.read(&buf), Err(AccessError::OutOfBounds));
+        let huge: Address = "%MW18446744073709551615".parse().unwrap();
+        assert_eq!(huge.read(&buf), Err(AccessError::OutOfBounds));
+        assert_eq!(
+            huge.write(&mut buf, Value::Word(0)),
+            Err(AccessError::OutOfBounds)
+        );
+        assert_eq!(
+            word.write(&mut buf, Value::Byte(1)),
+            Err(AccessError::TypeMismatch)
+        );
+
+        let dword: Address = "%MD4".parse().unwrap();
+        dword.write(&mut buf, Value::Real(-2.0)).unwrap();
+        assert_eq!(buf[4..], [0xc0, 0x00, 0x00, 0x00]);
+        word.write(&mut buf, Value::Int(-2)).unwrap();
+        assert_eq!(word.read(&buf), Ok(Value::Word(0xfffe)));
+    }
+
+    #[test]
+    fn word_bits() {
+        let mut buf = [0x00; 8];
+
+        let bit: Address = "%MW2.12".parse().unwrap();
+        assert_eq!(bit, Address::new_bit_in(Area::Memory, Width::Word, 2, 12));
+        assert_eq!(bit.bit_offset(), Some(12));
+        bit.write(&mut buf, Value::Bool(true)).unwrap();
+        assert_eq!(buf[2..4], [0x10, 0x00]);
+        bit.write_with_byte_order::<crate::LittleEndian>(&mut buf, Value::Bool(true))
+            .unwrap();
+        assert_eq!(buf[2..4], [0x10, 0x10]);
+        assert_eq!(bit.read(&buf), Ok(Value::Bool(true)));
+        assert_eq!(
+            bit.write(&mut buf, Value::Word(1)),
+            Err(AccessError::TypeMismatch)
+        );
+
+        let bit: Address = "%MD4.0".parse().unwrap();
+        bit.write_with_byte_order::<crate::WordSwapped>(&mut buf, Value::Bool(true))
+            .unwrap();
+        assert_eq!(buf[4..], [0x00, 0x01, 0x00, 0x00]);
+        assert_eq!(
+            bit.read_with_byte_order::<crate::WordSwapped>(&buf),
+            Ok(Value::Bool(true))
+        );
+        assert_eq!(bit.read(&buf), Ok(Value::Bool(false)));
+        assert_eq!(
+            "%ML0.63".parse::<Address>().unwrap().read(&buf),
+            Ok(Value::Bool(false))
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+use crate::access::BitField;
+use crate::byte_order::{BigEndian, ByteOrder};
+
+/// Process image buffer of `N` bytes for atomic access from multiple threads.
+///
+/// The buffer is only ever accessed through the aligned 64-bit words which contain the tags.  Bits
+/// and bytes are updated with read-modify-write operations on their word, so tags of different
+/// widths can be accessed concurrently, even when they overlap.  Tags are accessed with
+/// [`tag_atomic!()`][`crate::tag_atomic`] and the atomic structs generated by
+/// [`process_image!{}`][`crate::process_image`].
+#[repr(C, align(8))]
+pub struct AtomicBuffer<const N: usize> {
+    bytes: [AtomicU8; N],
+    /// Padding which extends the buffer to a whole number of words.
+    tail: [AtomicU8; 7],
+}
+
+impl<const N: usize> AtomicBuffer<N> {
+    /// Create a zeroed buffer.
+    pub const fn new() -> Self {
+        Self {
+            bytes: [const { AtomicU8::new(0) }; N],
+            tail: [const { AtomicU8::new(0) }; 7],
+        }
+    }
+
+    /// Copy of the buffer contents.
+    ///
+    /// Each word of 8 bytes is loaded individually, so tags in different words can be from
+    /// before and after a concurrent write.
+    pub fn load(&self) -> [u8; N] {
+        let mut copy = [0x00; N];
+        for (chunk, word) in copy.chunks_mut(8).zip(self.words()) {
+            chunk.copy_from_slice(&word.load(Ordering::Relaxed).to_ne_bytes()[..chunk.len()]);
+        }
+        copy
+    }
+
+    /// Accessor for bit `bit` of the byte at `addr`.
+    ///
+    /// # Panics
+    /// Panics when the address is outside of the buffer or the bit index is larger than 7.
+    #[inline(always)]
+    pub fn bit(&self, addr: usize, bit: u8) -> AtomicBit<'_> {
+        assert!(bit < 8, "Bit index must be in the range 0..=7");
+        let (word, offset) = self.word_of(addr, 1);
+        let mut mask = [0x00; 8];
+        mask[offset] = 1 << bit;
+        AtomicBit {
+            word,
+            mask: u64::from_ne_bytes(mask),
+        }
+    }
+
+    /// Accessor for the byte at `addr`.
+    ///
+    /// # Panics
+    /// Panics when the address is outside of the buffer.
+    #[inline(always)]
+    pub fn byte(&self, addr: usize) -> AtomicByte<'_> {
+        let (word, offset) = self.word_of(addr, 1);
+        AtomicByte { word, offset }
+    }
+
+    /// Accessor for the bits `low..=high` of the byte or word of type `T` at `addr`.
+    ///
+    /// # Panics
+    /// Panics when the address is outside of the buffer or not aligned to the size of `T`.
+    #[inline(always)]
+    pub fn bit_field<T: BitField, O: ByteOrder>(
+        &self,
+        addr: usize,
+        low: u8,
+        high: u8,
+    ) -> AtomicBitField<'_, T, O> {
+        let size = core::mem::size_of::<T>();
+        assert!(
+            addr % size == 0,
+            "Bit field must be aligned in memory for atomic access",
+        );
+        let (word, offset) = self.word_of(addr, size);
+        AtomicBitField {
+            word,
+            offset,
+            low,
+            high,
+            ty: PhantomData,
+        }
+    }
+
+    /// The word containing the `size` bytes at `addr` and the offset of `addr` in it.
+    #[inline(always)]
+    fn word_of(&self, addr: usize, size: usize) -> (&AtomicU64, usize) {
+        assert!(
+            addr < N && N - addr >= size,
+            "Address {} is outside of the atomic buffer",
+            addr,
+        );
+        (&self.words()[addr / 8], addr % 8)
+    }
+
+    #[inline(always)]
+    fn words(&self) -> &[AtomicU64] {
+        // SAFETY: The buffer is aligned to 8 bytes and `tail` extends `bytes` to at least the next
+        // multiple of 8 bytes, so all words lie within `self`.  The bytes are never accessed in
+        // any other way after construction, so there are no atomic accesses of different sizes.
+        unsafe { core::slice::from_raw_parts((self as *const Self).cast(), N.div_ceil(8)) }
+    }
+}
+
+impl<const N: usize> Default for AtomicBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> core::fmt::Debug for AtomicBuffer<N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("AtomicBuffer").field(&self.load()).finish()
+    }
+}
+
+/// Update the bytes of a word with a compare-and-swap loop.
+#[inline(always)]
+fn update(word: &AtomicU64, order: Ordering, f: impl Fn(&mut [u8; 8])) {
+    let _ = word.fetch_update(order, Ordering::Relaxed, |value| {
+        let mut bytes = value.to_ne_bytes();
+        f(&mut bytes);
+        Some(u64::from_ne_bytes(bytes))
+    });
+}
+
+/// Atomic accessor for a single bit.
+///
+/// Bits are set and cleared with `fetch_or()` and `fetch_and()` on their word, so concurrent
+/// writes to other bits of the same byte are never lost.
+#[derive(Debug, Clone, Copy)]
+pub struct AtomicBit<'a> {
+    word: &'a AtomicU64,
+    mask: u64,
+}
+
+impl AtomicBit<'_> {
+    /// Load the value of the bit.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> bool {
+        self.word.load(order) & self.mask != 0
+    }
+
+    /// Store a value into the bit.
+    #[inline(always)]
+    pub fn store(&self, value: bool, order: Ordering) {
+        if value {
+            self.word.fetch_or(self.mask, order);
+        } else {
+            self.word.fetch_and(!self.mask, order);
+        }
+    }
+
+    /// Invert the bit and return its previous value.
+    #[inline(always)]
+    pub fn toggle(&self, order: Ordering) -> bool {
+        self.word.fetch_xor(self.mask, order) & self.mask != 0
+    }
+}
+
+/// Atomic accessor for a byte.
+///
+/// `SINT` tags are accessed as `u8` as well, convert with `as i8`.
+#[derive(Debug, Clone, Copy)]
+pub struct AtomicByte<'a> {
+    word: &'a AtomicU64,
+    offset: usize,
+}
+
+impl AtomicByte<'_> {
+    /// Load the value of the tag.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> u8 {
+        self.word.load(order).to_ne_bytes()[self.offset]
+    }
+
+    /// Store a value into the tag.
+    #[inline(always)]
+    pub fn store(&self, value: u8, order: Ordering) {
+        update(self.word, order, |bytes| bytes[self.offset] = value);
+    }
+}
+
+/// Atomic accessor for a bit field.
+///
+/// The bits of the field are replaced with a compare-and-swap loop on their word, so concurrent
+/// writes to the other bits are never lost.  The byte order is selected by the type parameter `O`.
+#[derive(Debug, Clone, Copy)]
+pub struct AtomicBitField<'a, T: BitField, O: ByteOrder = BigEndian> {
+    word: &'a AtomicU64,
+    offset: usize,
+    low: u8,
+    high: u8,
+    ty: PhantomData<(T, O)>,
+}
+
+impl<T: BitField, O: ByteOrder> AtomicBitField<'_, T, O> {
+    /// Load the value of the bit field.
+    #[inline(always)]
+    pub fn load(&self, order: Ordering) -> T {
+        let bytes = self.word.load(order).to_ne_bytes();
+        T::read::<O>(&bytes[self.offset..]).bits(self.low, self.high)
+    }
+
+    /// Store a value into the bit field.  Bits of the value beyond the width of the field are
+    /// ignored.
+    #[inline(always)]
+    pub fn store(&self, value: T, order: Ordering) {
+        update(self.word, order, |bytes| {
+            T::read::<O>(&bytes[self.offset..])
+                .with_bits(self.low, self.high, value)
+                .write::<O>(&mut bytes[self.offset..]);
+        });
+    }
+}
+
+macro_rules! atomic_word {
+    (
+        $(#[$meta:meta])*
+        $Name:ident, $method:ident, $int:ident, $size:literal, $read:ident, $write:ident,
+        $width:literal
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $Name<'a, O: ByteOrder = BigEndian> {
+            word: &'a AtomicU64,
+            offset: usize,
+            order: PhantomData<O>,
+        }
+
+        impl<O: ByteOrder> $Name<'_, O> {
+            /// Load the value of the tag.
+            #[inline(always)]
+            pub fn load(&self, order: Ordering) -> $int {
+                let bytes = self.word.load(order).to_ne_bytes();
+                O::$read(bytes[self.offset..self.offset + $size].try_into().unwrap())
+            }
+
+            /// Store a value into the tag.
+            #[inline(always)]
+            pub fn store(&self, value: $int, order: Ordering) {
+                update(self.word, order, |bytes| {
+                    bytes[self.offset..self.offset + $size].copy_from_slice(&O::$write(value))
+                });
+            }
+        }
+
+        impl<const N: usize> AtomicBuffer<N> {
+            /// Accessor for the tag at `addr`.
+            ///
+            /// # Panics
+            /// Panics when the address is outside of the buffer or not aligned to the size of the
+            /// tag.
+            #[inline(always)]
+            pub fn $method<O: ByteOrder>(&self, addr: usize) -> $Name<'_, O> {
+                assert!(
+                    addr % $size == 0,
+                    ::core::concat!($width, " must be aligned in memory for atomic access"),
+                );
+                let (word, offset) = self.word_of(addr, $size);
+                $Name {
+                    word,
+                    offset,
+                    order: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+atomic_word!(
+    /// Atomic accessor for a word.
+    ///
+    /// The word is loaded and stored with a single atomic operation.  The byte order is selected by
+    /// the type parameter `O`.  `INT` tags are accessed as `u16` as well, convert with `as i16`.
+    AtomicWord, word, u16, 2, read_u16, write_u16, "Word"
+);
+
+atomic_word!(
+    /// Atomic accessor for a double word.
+    ///
+    /// The double word is loaded and stored with a single atomic operation.  The byte order is
+    /// selected by the type parameter `O`.  `DINT` and `REAL` tags are accessed as `u32` as well,
+    /// convert with `as i32` or `f32::from_bits()`.
+    AtomicDWord, dword, u32, 4, read_u32, write_u32, "Double word"
+);
+
+atomic_word!(
+    /// Atomic accessor for a long word.
+    ///
+    /// The long word is loaded and stored with a single atomic operation.  The byte order is
+    /// selected by the type parameter `O`.  `LINT` and `LREAL` tags are accessed as `u64` as well,
+    /// convert with `as i64` or `f64::from_bits()`.
+    AtomicLWord, lword, u64, 8, read_u64, write_u64, "Long word"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LittleEndian;
+
+    #[test]
+    fn bits_are_not_lost() {
+        let buf = AtomicBuffer::<1>::new();
+        std::thread::scope(|s| {
+            for bit in 0..8 {
+                let buf = &buf;
+                s.spawn(move || {
+                    let bit = buf.bit(0, bit);
+                    for _ in 0..1_000 {
+                        bit.store(true, Ordering::Relaxed);
+                        bit.store(false, Ordering::Relaxed);
+                    }
+                    bit.store(true, Ordering::Relaxed);
+                });
+            }
+        });
+        assert_eq!(buf.load(), [0xff]);
+
+        let bit = buf.bit(0, 3);
+        assert!(bit.toggle(Ordering::Relaxed));
+        assert!(!bit.load(Ordering::Relaxed));
+        assert_eq!(buf.load(), [0xf7]);
+    }
+
+    #[test]
+    fn words() {
+        let buf = AtomicBuffer::<16>::new();
+        let word = buf.word::<BigEndian>(2);
+        word.store(0x1234, Ordering::Relaxed);
+        assert_eq!(word.load(Ordering::Relaxed), 0x1234);
+
+        buf.dword::<LittleEndian>(4)
+            .store(0xdeadbeef, Ordering::Relaxed);
+        buf.lword::<BigEndian>(8).store(u64::MAX, Ordering::Relaxed);
+        buf.byte(1).store(0x42, Ordering::Relaxed);
+
+        assert_eq!(
+            buf.load(),
+            [
+                0x00, 0x42, 0x12, 0x34, 0xef, 0xbe, 0xad, 0xde, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                0xff, 0xff
+            ]
+        );
+    }
+
+    #[test]
+    fn overlapping_widths() {
+        let buf = AtomicBuffer::<4>::new();
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    buf.word::<BigEndian>(2).store(0x1234, Ordering::Relaxed);
+                }
+            });
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    buf.bit(0, 0).toggle(Ordering::Relaxed);
+                    buf.byte(1).store(0x55, Ordering::Relaxed);
+                }
+            });
+        });
+        assert_eq!(buf.load(), [0x00, 0x55, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn bit_fields() {
+        let buf = AtomicBuffer::<2>::new();
+        std::thread::scope(|s| {
+            for low in [0, 4, 8, 12] {
+                let buf = &buf;
+                s.spawn(move || {
+                    let field = buf.bit_field::<u16, LittleEndian>(0, low, low + 3);
+                    for value in 0..1_000 {
+                        field.store(value, Ordering::Relaxed);
+                    }
+                    field.store(low.into(), Ordering::Relaxed);
+                });
+            }
+        });
+        assert_eq!(buf.load(), [0x40, 0xc8]);
+        assert_eq!(
+            buf.bit_field::<u16, BigEndian>(0, 4, 11)
+                .load(Ordering::Relaxed),
+            0x0c
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Word must be aligned in memory for atomic access")]
+    fn unaligned_word() {
+        let buf = AtomicBuffer::<4>::new();
+        buf.word::<BigEndian>(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Address 6 is outside of the atomic buffer")]
+    fn out_of_bounds() {
+        let buf = AtomicBuffer::<7>::new();
+        buf.word::<BigEndian>(6);
+    }
+}
@@ -0,0 +1,212 @@
+/// Byte order of multi-byte tags in a process image.
+///
+/// This trait is implemented by marker types which are used as type parameters for the mutable
+/// accessors and by the code generated from the macros.  As everything is resolved at compile
+/// time, the choice of byte order has no runtime cost.
+pub trait ByteOrder {
+    /// Convert the bytes of a word into its value.
+    fn read_u16(bytes: [u8; 2]) -> u16;
+    /// Convert the bytes of a double word into its value.
+    fn read_u32(bytes: [u8; 4]) -> u32;
+    /// Convert the bytes of a long word into its value.
+    fn read_u64(bytes: [u8; 8]) -> u64;
+
+    /// Convert the value of a word into its bytes.
+    fn write_u16(value: u16) -> [u8; 2];
+    /// Convert the value of a double word into its bytes.
+    fn write_u32(value: u32) -> [u8; 4];
+    /// Convert the value of a long word into its bytes.
+    fn write_u64(value: u64) -> [u8; 8];
+}
+
+/// Big-endian (MSB-first) byte order.
+///
+/// This is the default byte order, used e.g. by Siemens and PROFIBUS/PROFINET devices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigEndian;
+
+impl ByteOrder for BigEndian {
+    #[inline(always)]
+    fn read_u16(bytes: [u8; 2]) -> u16 {
+        u16::from_be_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn read_u32(bytes: [u8; 4]) -> u32 {
+        u32::from_be_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn read_u64(bytes: [u8; 8]) -> u64 {
+        u64::from_be_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn write_u16(value: u16) -> [u8; 2] {
+        value.to_be_bytes()
+    }
+
+    #[inline(always)]
+    fn write_u32(value: u32) -> [u8; 4] {
+        value.to_be_bytes()
+    }
+
+    #[inline(always)]
+    fn write_u64(value: u64) -> [u8; 8] {
+        value.to_be_bytes()
+    }
+}
+
+/// Little-endian (LSB-first) byte order.
+///
+/// Used e.g. by EtherCAT and most x86-based soft-PLCs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LittleEndian;
+
+impl ByteOrder for LittleEndian {
+    #[inline(always)]
+    fn read_u16(bytes: [u8; 2]) -> u16 {
+        u16::from_le_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn read_u32(bytes: [u8; 4]) -> u32 {
+        u32::from_le_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn read_u64(bytes: [u8; 8]) -> u64 {
+        u64::from_le_bytes(bytes)
+    }
+
+    #[inline(always)]
+    fn write_u16(value: u16) -> [u8; 2] {
+        value.to_le_bytes()
+    }
+
+    #[inline(always)]
+    fn write_u32(value: u32) -> [u8; 4] {
+        value.to_le_bytes()
+    }
+
+    #[inline(always)]
+    fn write_u64(value: u64) -> [u8; 8] {
+        value.to_le_bytes()
+    }
+}
+
+/// Big-endian byte order with swapped 16-bit words ("CDAB").
+///
+/// Many Modbus devices transfer 32-bit and 64-bit values as multiple 16-bit registers, with the
+/// least significant register first.  The bytes inside each register are big-endian.  For a double
+/// word `0xAABBCCDD`, the bytes in the process image are `CC DD AA BB`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WordSwapped;
+
+impl ByteOrder for WordSwapped {
+    #[inline(always)]
+    fn read_u16(bytes: [u8; 2]) -> u16 {
+        u16::from_le_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn read_u32(bytes: [u8; 4]) -> u32 {
+        u32::from_le_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn read_u64(bytes: [u8; 8]) -> u64 {
+        u64::from_le_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn write_u16(value: u16) -> [u8; 2] {
+        swap_pairs(value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u32(value: u32) -> [u8; 4] {
+        swap_pairs(value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u64(value: u64) -> [u8; 8] {
+        swap_pairs(value.to_le_bytes())
+    }
+}
+
+/// Big-endian byte order with swapped bytes in each 16-bit word ("BADC").
+///
+/// The 16-bit words are transferred most significant word first, but the bytes inside each word
+/// are little-endian.  For a double word `0xAABBCCDD`, the bytes in the process image are
+/// `BB AA DD CC`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteSwapped;
+
+impl ByteOrder for ByteSwapped {
+    #[inline(always)]
+    fn read_u16(bytes: [u8; 2]) -> u16 {
+        u16::from_be_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn read_u32(bytes: [u8; 4]) -> u32 {
+        u32::from_be_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn read_u64(bytes: [u8; 8]) -> u64 {
+        u64::from_be_bytes(swap_pairs(bytes))
+    }
+
+    #[inline(always)]
+    fn write_u16(value: u16) -> [u8; 2] {
+        swap_pairs(value.to_be_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u32(value: u32) -> [u8; 4] {
+        swap_pairs(value.to_be_bytes())
+    }
+
+    #[inline(always)]
+    fn write_u64(value: u64) -> [u8; 8] {
+        swap_pairs(value.to_be_bytes())
+    }
+}
+
+/// Swap the two bytes of each 16-bit word.
+#[inline(always)]
+fn swap_pairs<const N: usize>(mut bytes: [u8; N]) -> [u8; N] {
+    for pair in bytes.chunks_exact_mut(2) {
+        pair.swap(0, 1);
+    }
+    bytes
+}
+
+/// Map the byte order names used in the macros to the marker types.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! byte_order {
+    () => {
+        $crate::BigEndian
+    };
+    (big_endian) => {
+        $crate::BigEndian
+    };
+    (little_endian) => {
+        $crate::LittleEndian
+    };
+    (ABCD) => {
+        $crate::BigEndian
+    };
+    (CDAB) => {
+        $crate::WordSwapped
+    };
+    (BADC) => {
+        $crate::ByteSwapped
+    };
+    (DCBA) => {
+        $crate::LittleEndian
+    };
+}
@@ -0,0 +1,84 @@
+use core::cell::Cell;
+
+use crate::byte_order::ByteOrder;
+
+/// Value type of a tag in a cell-based process image.
+///
+/// This is implemented for all integer and floating point types of the tags.
+#[doc(hidden)]
+pub trait CellValue: Copy {
+    fn get<O: ByteOrder>(cells: &[Cell<u8>]) -> Self;
+
+    fn set<O: ByteOrder>(cells: &[Cell<u8>], value: Self);
+}
+
+impl CellValue for u8 {
+    #[inline(always)]
+    fn get<O: ByteOrder>(cells: &[Cell<u8>]) -> Self {
+        cells[0].get()
+    }
+
+    #[inline(always)]
+    fn set<O: ByteOrder>(cells: &[Cell<u8>], value: Self) {
+        cells[0].set(value);
+    }
+}
+
+impl CellValue for i8 {
+    #[inline(always)]
+    fn get<O: ByteOrder>(cells: &[Cell<u8>]) -> Self {
+        cells[0].get() as i8
+    }
+
+    #[inline(always)]
+    fn set<O: ByteOrder>(cells: &[Cell<u8>], value: Self) {
+        cells[0].set(value as u8);
+    }
+}
+
+macro_rules! cell_value {
+    ($($ty:ident: $size:literal, $read:ident, $write:ident, |$v:ident| $from:expr, $into:expr;)*) => {
+        $(
+            impl CellValue for $ty {
+                #[inline(always)]
+                fn get<O: ByteOrder>(cells: &[Cell<u8>]) -> Self {
+                    let $v = O::$read(load::<$size>(cells));
+                    $from
+                }
+
+                #[inline(always)]
+                fn set<O: ByteOrder>(cells: &[Cell<u8>], value: Self) {
+                    let $v = value;
+                    store(cells, O::$write($into));
+                }
+            }
+        )*
+    };
+}
+
+cell_value! {
+    u16: 2, read_u16, write_u16, |v| v, v;
+    u32: 4, read_u32, write_u32, |v| v, v;
+    u64: 8, read_u64, write_u64, |v| v, v;
+    i16: 2, read_u16, write_u16, |v| v as i16, v as u16;
+    i32: 4, read_u32, write_u32, |v| v as i32, v as u32;
+    i64: 8, read_u64, write_u64, |v| v as i64, v as u64;
+    f32: 4, read_u32, write_u32, |v| f32::from_bits(v), v.to_bits();
+    f64: 8, read_u64, write_u64, |v| f64::from_bits(v), v.to_bits();
+}
+
+/// Copy of the first `S` cells.
+#[doc(hidden)]
+#[inline(always)]
+pub fn load<const S: usize>(cells: &[Cell<u8>]) -> [u8; S] {
+    let cells = &cells[..S];
+    core::array::from_fn(|i| cells[i].get())
+}
+
+/// Overwrite the first `S` cells.
+#[inline(always)]
+fn store<const S: usize>(cells: &[Cell<u8>], bytes: [u8; S]) {
+    for (cell, byte) in cells[..S].iter().zip(bytes) {
+        cell.set(byte);
+    }
+}
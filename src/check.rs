@@ -0,0 +1,417 @@
+//! Compile-time checks for the tag tables of [`process_image!{}`][`crate::process_image`].
+//!
+//! Everything in here is only used by the code generated from the macros and is not part of the
+//! public API.
+
+#[doc(hidden)]
+#[inline(always)]
+pub const fn tag_aligned(addr: usize, size: usize, align: usize) -> bool {
+    addr % crate::required_alignment(size, align) == 0
+}
+
+#[doc(hidden)]
+#[inline(always)]
+pub const fn tag_in_bounds(addr: usize, size: usize, image_size: usize) -> bool {
+    addr + size <= image_size
+}
+
+/// Position of the bytes of multi-byte tags in the process image, for overlap detection.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct ByteLayout {
+    /// The most significant byte comes first.
+    reversed: bool,
+    /// The two bytes of each 16-bit word are swapped.
+    swapped: bool,
+}
+
+impl ByteLayout {
+    /// Offset of byte `byte` of a tag of `size` bytes, counted from the least significant byte.
+    const fn physical_byte(self, byte: usize, size: usize) -> usize {
+        if size == 1 {
+            return 0;
+        }
+        let byte = if self.reversed { size - 1 - byte } else { byte };
+        if self.swapped {
+            byte ^ 1
+        } else {
+            byte
+        }
+    }
+}
+
+impl crate::BigEndian {
+    #[doc(hidden)]
+    pub const LAYOUT: ByteLayout = ByteLayout {
+        reversed: true,
+        swapped: false,
+    };
+}
+
+impl crate::LittleEndian {
+    #[doc(hidden)]
+    pub const LAYOUT: ByteLayout = ByteLayout {
+        reversed: false,
+        swapped: false,
+    };
+}
+
+impl crate::WordSwapped {
+    #[doc(hidden)]
+    pub const LAYOUT: ByteLayout = ByteLayout {
+        reversed: false,
+        swapped: true,
+    };
+}
+
+impl crate::ByteSwapped {
+    #[doc(hidden)]
+    pub const LAYOUT: ByteLayout = ByteLayout {
+        reversed: true,
+        swapped: true,
+    };
+}
+
+/// The bits occupied by a tag, for overlap detection.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub struct TagExtent {
+    addr: usize,
+    size: usize,
+    alias: bool,
+    /// Occupied bits of each byte of the tag, byte `i` in bits `8 * i..8 * i + 8`.
+    bits: u64,
+}
+
+impl TagExtent {
+    #[doc(hidden)]
+    pub const fn new(addr: usize, size: usize, alias: bool) -> Self {
+        Self {
+            addr,
+            size,
+            alias,
+            bits: u64::MAX >> (64 - 8 * size),
+        }
+    }
+
+    /// Restrict the extent of a byte or word tag to the bits `low..=high`.
+    ///
+    /// The bits are counted from the least significant bit of the tag, so the byte which holds
+    /// each bit depends on the byte order.
+    #[doc(hidden)]
+    pub const fn bit_field(self, low: u32, high: u32, layout: ByteLayout) -> Self {
+        let mut bits = 0;
+        let mut bit = low as usize;
+        while bit <= high as usize && bit < 8 * self.size {
+            let byte = layout.physical_byte(bit / 8, self.size);
+            bits |= 1 << (8 * byte + bit % 8);
+            bit += 1;
+        }
+        Self { bits, ..self }
+    }
+
+    /// Whether both tags occupy a common bit.
+    const fn overlaps(&self, other: &Self) -> bool {
+        let (first, second) = if self.addr <= other.addr {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if first.addr + first.size <= second.addr {
+            return false;
+        }
+        (first.bits >> (8 * (second.addr - first.addr))) & second.bits != 0
+    }
+}
+
+/// Check whether a tag overlaps any other tag of the list.
+///
+/// The list must contain the tag itself.  Tags marked as an alias never overlap.  Bit fields and
+/// bits of words only overlap the tags which occupy one of their bits in the process image.
+#[doc(hidden)]
+pub const fn tag_overlaps(tags: &[TagExtent], tag: TagExtent) -> bool {
+    if tag.alias {
+        return false;
+    }
+
+    let mut overlapping = 0;
+    let mut i = 0;
+    while i < tags.len() {
+        let other = tags[i];
+        if !other.alias && tag.overlaps(&other) {
+            overlapping += 1;
+        }
+        i += 1;
+    }
+
+    // The tag always overlaps itself.
+    overlapping > 1
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! image_check {
+    ($align:expr) => {
+        const _: () = assert!(
+            $align == 1 || $align == 2 || $align == 4 || $align == 8,
+            "Alignment of a process image must be 1, 2, 4, or 8",
+        );
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! area_check {
+    (mut, [I]) => {
+        ::core::compile_error!("Process images of inputs (`in I`) cannot be mutable");
+    };
+    ($kind:ident, $area:tt) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_check {
+    ($name:ident, $SIZE:literal, $align:expr, $kind:ident @ $order:ident, $($addr:tt)+) => {
+        $crate::tag_check!($name, $SIZE, $align, $kind, $($addr)+);
+    };
+    (
+        $name:ident, $SIZE:literal, $align:expr,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $crate::tag_check!(@bit_field $name, $SIZE, $align, $kind, $addr, $low, $high);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, X, $addr1:literal, $addr2:literal) => {
+        $crate::tag_check!($name, $SIZE, $align, $addr1, $addr2);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, W, $addr:literal, $bit:literal) => {
+        $crate::tag_check!(@bit_in $name, $SIZE, $align, W, $addr, $bit, 15);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, D, $addr:literal, $bit:literal) => {
+        $crate::tag_check!(@bit_in $name, $SIZE, $align, D, $addr, $bit, 31);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, L, $addr:literal, $bit:literal) => {
+        $crate::tag_check!(@bit_in $name, $SIZE, $align, L, $addr, $bit, 63);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, $kind:ident, $addr:literal, $bit:literal) => {
+        ::core::compile_error!(::core::concat!(
+            "Bit tag `",
+            ::core::stringify!($name),
+            "` must be an X, W, D, or L tag",
+        ));
+    };
+    ($name:ident, $SIZE:literal, $align:expr, B, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 1, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, SINT, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 1, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, W, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 2, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, INT, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 2, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, D, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 4, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, DINT, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 4, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, REAL, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 4, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, L, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 8, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, LINT, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 8, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, LREAL, $addr:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 8, $addr);
+    };
+    ($name:ident, $SIZE:literal, $align:expr, $addr1:literal, $addr2:literal) => {
+        $crate::tag_check!(@range $name, $SIZE, $align, 1, $addr1);
+        const _: () = assert!(
+            $addr2 < 8,
+            ::core::concat!(
+                "Bit index of tag `",
+                ::core::stringify!($name),
+                "` must be in the range 0..=7",
+            ),
+        );
+    };
+    (
+        @bit_field $name:ident, $SIZE:literal, $align:expr,
+        B, $addr:literal, $low:literal, $high:literal
+    ) => {
+        $crate::tag_check!(@bits $name, $SIZE, $align, B, $addr, $low, $high, 7);
+    };
+    (
+        @bit_field $name:ident, $SIZE:literal, $align:expr,
+        W, $addr:literal, $low:literal, $high:literal
+    ) => {
+        $crate::tag_check!(@bits $name, $SIZE, $align, W, $addr, $low, $high, 15);
+    };
+    (
+        @bit_field $name:ident, $SIZE:literal, $align:expr,
+        D, $addr:literal, $low:literal, $high:literal
+    ) => {
+        $crate::tag_check!(@bits $name, $SIZE, $align, D, $addr, $low, $high, 31);
+    };
+    (
+        @bit_field $name:ident, $SIZE:literal, $align:expr,
+        L, $addr:literal, $low:literal, $high:literal
+    ) => {
+        $crate::tag_check!(@bits $name, $SIZE, $align, L, $addr, $low, $high, 63);
+    };
+    (
+        @bit_field $name:ident, $SIZE:literal, $align:expr,
+        $kind:ident, $addr:literal, $low:literal, $high:literal
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "Bit field tag `",
+            ::core::stringify!($name),
+            "` must be a B, W, D, or L tag",
+        ));
+    };
+    (
+        @bits $name:ident, $SIZE:literal, $align:expr,
+        $kind:ident, $addr:literal, $low:literal, $high:literal, $max:literal
+    ) => {
+        $crate::tag_check!($name, $SIZE, $align, $kind, $addr);
+        const _: () = assert!(
+            $low <= $high && $high <= $max,
+            ::core::concat!(
+                "Bits of tag `",
+                ::core::stringify!($name),
+                "` must be an ascending range within 0..=",
+                $max,
+            ),
+        );
+    };
+    (
+        @bit_in $name:ident, $SIZE:literal, $align:expr,
+        $kind:ident, $addr:literal, $bit:literal, $max:literal
+    ) => {
+        $crate::tag_check!($name, $SIZE, $align, $kind, $addr);
+        const _: () = assert!(
+            $bit <= $max,
+            ::core::concat!(
+                "Bit index of tag `",
+                ::core::stringify!($name),
+                "` must be in the range 0..=",
+                $max,
+            ),
+        );
+    };
+    (@range $name:ident, $SIZE:literal, $align:expr, $size:literal, $addr:literal) => {
+        const _: () = assert!(
+            $crate::check::tag_in_bounds($addr, $size, $SIZE),
+            ::core::concat!(
+                "Tag `",
+                ::core::stringify!($name),
+                "` exceeds the size of the process image",
+            ),
+        );
+        const _: () = assert!(
+            $crate::check::tag_aligned($addr, $size, $align),
+            ::core::concat!("Address of tag `", ::core::stringify!($name), "` is not aligned"),
+        );
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! overlap_check {
+    ([], $order:ty; $($tags:tt)*) => {};
+    ([no_overlap], $order:ty; $(
+        [$($attr:tt)*] $name:ident: ($($tag:tt)+)
+    ),*) => {
+        const _: () = {
+            let tags: &[$crate::check::TagExtent] = &[
+                $( $crate::tag_extent!([$($attr)*] $order; $($tag)+) ),*
+            ];
+            $(
+                assert!(
+                    !$crate::check::tag_overlaps(
+                        tags,
+                        $crate::tag_extent!([$($attr)*] $order; $($tag)+),
+                    ),
+                    ::core::concat!(
+                        "Tag `",
+                        ::core::stringify!($name),
+                        "` overlaps with another tag",
+                    ),
+                );
+            )*
+        };
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_extent {
+    ([$($attr:tt)*] $order:ty; $kind:ident @ $tag_order:ident, $($addr:tt)+) => {
+        $crate::tag_extent!([$($attr)*] $crate::byte_order!($tag_order); $kind, $($addr)+)
+    };
+    ([$($attr:tt)*] $order:ty; $kind:ident, $addr:literal, $low:literal ..= $high:literal) => {
+        $crate::tag_extent!([$($attr)*] $order; $kind, $addr)
+            .bit_field($low, $high, <$order>::LAYOUT)
+    };
+    ([$($attr:tt)*] $order:ty; X, $addr1:literal, $addr2:literal) => {
+        $crate::tag_extent!([$($attr)*] $order; $addr1, $addr2)
+    };
+    ([$($attr:tt)*] $order:ty; $kind:ident, $addr:literal, $bit:literal) => {
+        $crate::tag_extent!([$($attr)*] $order; $kind, $addr)
+            .bit_field($bit, $bit, <$order>::LAYOUT)
+    };
+    ([$($attr:tt)*] $order:ty; B, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 1, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; SINT, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 1, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; W, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 2, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; INT, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 2, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; D, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 4, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; DINT, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 4, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; REAL, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 4, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; L, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 8, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; LINT, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 8, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; LREAL, $addr:literal) => {
+        $crate::check::TagExtent::new($addr, 8, $crate::tag_alias!($($attr)*))
+    };
+    ([$($attr:tt)*] $order:ty; $addr1:literal, $addr2:literal) => {
+        $crate::check::TagExtent::new($addr1, 1, $crate::tag_alias!($($attr)*))
+            .bit_field($addr2, $addr2, <$order>::LAYOUT)
+    };
+}
+
+/// Find out whether a tag is marked with the `#[alias]` attribute.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_alias {
+    () => {
+        false
+    };
+    (#[alias] $($rest:tt)*) => {
+        true
+    };
+    (#[$($attr:tt)*] $($rest:tt)*) => {
+        $crate::tag_alias!($($rest)*)
+    };
+}
@@ -0,0 +1,205 @@
+use core::iter::FusedIterator;
+use core::ops::Range;
+
+use crate::address::Width;
+use crate::tag_info::TagInfo;
+use crate::value::Value;
+
+/// Change of a tag between two snapshots of a process image.
+///
+/// Returned by the `diff()` method of process images, see [`Diff`].
+#[derive(Debug, Clone, Copy)]
+pub struct TagChange {
+    tag: &'static TagInfo,
+    old: Value,
+    new: Value,
+}
+
+impl TagChange {
+    /// Metadata of the changed tag.
+    pub fn tag(&self) -> &'static TagInfo {
+        self.tag
+    }
+
+    /// Value of the tag in the old snapshot.
+    pub fn old_value(&self) -> Value {
+        self.old
+    }
+
+    /// Value of the tag in the new snapshot.
+    pub fn new_value(&self) -> Value {
+        self.new
+    }
+}
+
+/// Iterator over the tags which differ between two snapshots of a process image.
+///
+/// This is returned by the `diff()` method of the structs generated by
+/// [`process_image!{}`][`crate::process_image`]:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiInputs: 4 in I {
+///         pub door_closed: (X, 0, 3),
+///         pub light_on: (X, 0, 4),
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let previous = [0x08, 0x00, 0x05, 0xdc];
+/// let current = [0x10, 0x00, 0x05, 0xdc];
+/// let previous = PiInputs::from(&previous);
+/// let current = PiInputs::from(&current);
+///
+/// for change in previous.diff(&current) {
+///     println!(
+///         "{} changed from {} to {}",
+///         change.tag().name(),
+///         change.old_value(),
+///         change.new_value(),
+///     );
+/// }
+///
+/// let changes: Vec<_> = previous.diff(&current).map(|c| c.tag().name()).collect();
+/// assert_eq!(changes, ["door_closed", "light_on"]);
+/// ```
+///
+/// A tag is changed when its raw data differs, so the bytes of each tag are compared before any
+/// value is decoded.  The snapshots are compared once up front to find the region between the
+/// first and the last changed byte; tags outside of it are skipped without looking at their data.
+/// When both snapshots are identical, no tag is looked at at all.
+#[derive(Debug, Clone)]
+pub struct Diff<'a> {
+    tags: core::slice::Iter<'static, TagInfo>,
+    changed: Range<usize>,
+    old: &'a [u8],
+    new: &'a [u8],
+}
+
+impl<'a> Diff<'a> {
+    #[doc(hidden)]
+    pub fn new(tags: &'static [TagInfo], old: &'a [u8], new: &'a [u8]) -> Self {
+        let differs = |(a, b): (&u8, &u8)| a != b;
+        let changed = match old.iter().zip(new).position(differs) {
+            Some(first) => {
+                let last = old.iter().zip(new).rposition(differs).unwrap_or(first);
+                first..last + 1
+            }
+            None => 0..0,
+        };
+        let tags = if changed.is_empty() { &[] } else { tags };
+        Self {
+            tags: tags.iter(),
+            changed,
+            old,
+            new,
+        }
+    }
+}
+
+impl Iterator for Diff<'_> {
+    type Item = TagChange;
+
+    fn next(&mut self) -> Option<TagChange> {
+        self.tags.find_map(|tag| {
+            let address = tag.address();
+            let start = address.byte_offset();
+            let end = start + address.width().size();
+            if end <= self.changed.start || start >= self.changed.end {
+                return None;
+            }
+            let (old, new) = (&self.old[start..end], &self.new[start..end]);
+            let changed = match (address.width(), address.bit_offset()) {
+                (Width::Bit, Some(bit)) => (old[0] ^ new[0]) & (1 << bit) != 0,
+                (_, None) if tag.bit_field().is_none() => old != new,
+                // Bits of words and bit fields only occupy some bits of their bytes.
+                _ => old != new && tag.read(self.old) != tag.read(self.new),
+            };
+            changed.then(|| TagChange {
+                tag,
+                old: tag.read(self.old),
+                new: tag.read(self.new),
+            })
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.tags.size_hint().1)
+    }
+}
+
+impl FusedIterator for Diff<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::process_image! {
+        pub struct TestPiBitFields: 2 @ little_endian {
+            pub state: (W, 0, 0..=3),
+            pub mode: (W, 0, 8..=10),
+        }
+    }
+
+    crate::process_image! {
+        pub struct TestPiWordBits: 2 @ little_endian {
+            pub switch_on: (W, 0, 0),
+            pub halt: (W, 0, 8),
+        }
+    }
+
+    #[test]
+    fn bit_fields() {
+        let old = [0x30, 0x02];
+        let mut new = old;
+        new[0] ^= 0x10;
+        assert_eq!(Diff::new(TestPiBitFields::TAGS, &old, &new).count(), 0);
+
+        new[0] ^= 0x01;
+        let changes: Vec<_> = Diff::new(TestPiBitFields::TAGS, &old, &new)
+            .map(|c| (c.tag().name(), c.old_value(), c.new_value()))
+            .collect();
+        assert_eq!(changes, [("state", Value::Word(0), Value::Word(1))]);
+    }
+
+    #[test]
+    fn word_bits() {
+        let old = [0x00, 0x00];
+        let mut new = old;
+        new[1] = 0x02;
+        assert_eq!(Diff::new(TestPiWordBits::TAGS, &old, &new).count(), 0);
+
+        new[1] = 0x03;
+        let changes: Vec<_> = Diff::new(TestPiWordBits::TAGS, &old, &new)
+            .map(|c| c.tag().name())
+            .collect();
+        assert_eq!(changes, ["halt"]);
+    }
+
+    crate::process_image! {
+        pub struct TestPiRegions: 6 {
+            pub first: (B, 0),
+            pub middle: (W, 2),
+            pub last: (B, 5),
+        }
+    }
+
+    #[test]
+    fn regions() {
+        let old = [0x00; 6];
+        assert_eq!(Diff::new(TestPiRegions::TAGS, &old, &old).count(), 0);
+
+        let mut new = old;
+        new[5] = 0x01;
+        let changes: Vec<_> = Diff::new(TestPiRegions::TAGS, &old, &new)
+            .map(|c| c.tag().name())
+            .collect();
+        assert_eq!(changes, ["last"]);
+
+        new[0] = 0x01;
+        let changes: Vec<_> = Diff::new(TestPiRegions::TAGS, &old, &new)
+            .map(|c| c.tag().name())
+            .collect();
+        assert_eq!(changes, ["first", "last"]);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::address::{AccessError, Address, Width};
+use crate::image::ProcessImage;
+use crate::tag_info::{DataType, TagInfo};
+use crate::value::Value;
+
+/// Force layer over a process image of `N` bytes, for commissioning and simulation.
+///
+/// Forced tags keep their forced value regardless of what the program writes.  The forces are
+/// stored as a value buffer and a bit mask.  Call [`Forces::apply_forces()`] on the process image
+/// after the program ran and before it is sent out (or after the inputs were received), so the
+/// forced bits win over everything else.
+///
+/// Tags are forced by their name or by their [`Address`], like in the `TAGS` table of the process
+/// image.
+///
+/// # Example
+/// ```
+/// use process_image::{Forces, Value};
+///
+/// process_image::process_image_owned! {
+///     pub struct PiOutputs, mut PiOutputsMut: 4 in Q {
+///         pub valve: (X, 0, 0),
+///         pub setpoint: (W, 2),
+///     }
+/// }
+///
+/// let mut forces = Forces::<4>::new::<PiOutputs>();
+/// forces.force("valve", Value::Bool(true)).unwrap();
+/// forces.force_address("%QW2".parse().unwrap(), Value::Word(800)).unwrap();
+///
+/// let mut pi = PiOutputs::new_zeroed();
+/// *pi.as_mut().setpoint() = 1500; // Program logic
+/// forces.apply_forces(pi.as_slice_mut());
+/// assert_eq!(pi.valve(), true);
+/// assert_eq!(pi.setpoint(), 800);
+///
+/// for (tag, value) in forces.iter() {
+///     println!("{} ({}) forced to {}", tag.name(), tag.address(), value);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Forces<const N: usize> {
+    tags: &'static [TagInfo],
+    values: [u8; N],
+    mask: [u8; N],
+}
+
+impl<const N: usize> Forces<N> {
+    /// Create an empty force layer for the process image `P`.
+    ///
+    /// `N` must be the size of the process image, this is checked at compile time.
+    pub fn new<P: ProcessImage>() -> Self {
+        const {
+            assert!(
+                N == P::SIZE,
+                "Forces must have the size of the process image"
+            );
+        }
+
+        Self {
+            tags: P::TAGS,
+            values: [0x00; N],
+            mask: [0x00; N],
+        }
+    }
+
+    /// Force the tag `name` to `value`.
+    ///
+    /// The value must be of the data type of the tag.
+    pub fn force(&mut self, name: &str, value: Value) -> Result<(), AccessError> {
+        let tag = self.tag(name)?;
+        self.force_tag(tag, value)
+    }
+
+    /// Force the tag at `address` to `value`.
+    pub fn force_address(&mut self, address: Address, value: Value) -> Result<(), AccessError> {
+        let tag = self.tag_at(address)?;
+        self.force_tag(tag, value)
+    }
+
+    /// Remove the force of the tag `name`.
+    pub fn unforce(&mut self, name: &str) -> Result<(), AccessError> {
+        let tag = self.tag(name)?;
+        self.set_mask(tag, false);
+        Ok(())
+    }
+
+    /// Remove the force of the tag at `address`.
+    pub fn unforce_address(&mut self, address: Address) -> Result<(), AccessError> {
+        let tag = self.tag_at(address)?;
+        self.set_mask(tag, false);
+        Ok(())
+    }
+
+    /// Remove all forces.
+    pub fn unforce_all(&mut self) {
+        self.values = [0x00; N];
+        self.mask = [0x00; N];
+    }
+
+    /// Whether the tag `name` is forced.
+    pub fn is_forced(&self, name: &str) -> bool {
+        self.tag(name).is_ok_and(|tag| self.covers(tag))
+    }
+
+    /// Whether no bit is forced at all.
+    pub fn is_empty(&self) -> bool {
+        self.mask.iter().all(|m| *m == 0)
+    }
+
+    /// Overwrite the forced bits of a process image buffer with their forced values.
+    ///
+    /// # Panics
+    /// Panics when `buf` is not `N` bytes long.
+    pub fn apply_forces(&self, buf: &mut [u8]) {
+        assert_eq!(
+            buf.len(),
+            N,
+            "buffer does not have the size of the process image"
+        );
+        for ((byte, value), mask) in buf.iter_mut().zip(&self.values).zip(&self.mask) {
+            *byte = (*byte & !mask) | (value & mask);
+        }
+    }
+
+    /// Iterate over all forced tags and their forced values.
+    ///
+    /// Tags which overlap a forced tag are listed when all of their bits are forced.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static TagInfo, Value)> + '_ {
+        self.tags
+            .iter()
+            .filter(|tag| self.covers(tag))
+            .map(|tag| (tag, tag.read(&self.values)))
+    }
+
+    fn tag(&self, name: &str) -> Result<&'static TagInfo, AccessError> {
+        self.tags
+            .iter()
+            .find(|tag| tag.name() == name)
+            .ok_or(AccessError::UnknownTag)
+    }
+
+    fn tag_at(&self, address: Address) -> Result<&'static TagInfo, AccessError> {
+        self.tags
+            .iter()
+            .find(|tag| tag.address() == address)
+            .ok_or(AccessError::UnknownTag)
+    }
+
+    fn force_tag(&mut self, tag: &TagInfo, value: Value) -> Result<(), AccessError> {
+        tag.write(&mut self.values, value)?;
+        self.set_mask(tag, true);
+        Ok(())
+    }
+
+    /// Mask of the bits of a tag in the buffers.
+    fn tag_mask(tag: &TagInfo) -> [u8; N] {
+        let mut mask = [0x00; N];
+        let address = tag.address();
+        let start = address.byte_offset();
+        match (address.width(), address.bit_offset()) {
+            (Width::Bit, Some(bit)) => mask[start] = 1 << bit,
+            (width, None) if tag.bit_field().is_none() => {
+                mask[start..start + width.size()].fill(0xff)
+            }
+            _ => {
+                // These bits depend on the byte order, so let the tag write them.
+                let ones = match tag.data_type() {
+                    DataType::Bool => Value::Bool(true),
+                    DataType::Byte => Value::Byte(u8::MAX),
+                    DataType::Word => Value::Word(u16::MAX),
+                    DataType::DWord => Value::DWord(u32::MAX),
+                    _ => Value::LWord(u64::MAX),
+                };
+                let _ = tag.write(&mut mask, ones);
+            }
+        }
+        mask
+    }
+
+    fn set_mask(&mut self, tag: &TagInfo, forced: bool) {
+        for (mask, bits) in self.mask.iter_mut().zip(Self::tag_mask(tag)) {
+            if forced {
+                *mask |= bits;
+            } else {
+                *mask &= !bits;
+            }
+        }
+    }
+
+    fn covers(&self, tag: &TagInfo) -> bool {
+        let bits = Self::tag_mask(tag);
+        self.mask
+            .iter()
+            .zip(bits)
+            .all(|(mask, bits)| mask & bits == bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::process_image_owned! {
+        pub struct TestPiOutputs, mut TestPiOutputsMut: 4 in Q {
+            pub valve: (X, 0, 0),
+            pub lamp: (X, 0, 1),
+            #[alias]
+            pub flags: (B, 0),
+            pub setpoint: (INT, 2),
+        }
+    }
+
+    crate::process_image! {
+        pub struct TestPiBitFields: 2 @ little_endian {
+            pub state: (W, 0, 0..=3),
+            pub mode: (W, 0, 8..=10),
+        }
+    }
+
+    crate::process_image! {
+        pub struct TestPiWordBits: 8 @ little_endian {
+            pub halt: (W, 0, 8),
+            pub fault: (W @ big_endian, 2, 3),
+            pub ready: "%MD4.31",
+        }
+    }
+
+    #[test]
+    fn force_and_apply() {
+        let mut forces = Forces::<4>::new::<TestPiOutputs>();
+        assert!(forces.is_empty());
+
+        forces.force("lamp", Value::Bool(true)).unwrap();
+        forces
+            .force_address("%QW2".parse().unwrap(), Value::Int(-10))
+            .unwrap();
+        assert!(forces.is_forced("lamp"));
+        assert!(!forces.is_forced("valve"));
+
+        let mut pi = TestPiOutputs::new_zeroed();
+        *pi.as_mut().flags() = 0x80;
+        *pi.as_mut().valve() = true;
+        *pi.as_mut().lamp() = false;
+        *pi.as_mut().setpoint() = 1500;
+        forces.apply_forces(pi.as_slice_mut());
+        assert!(pi.valve());
+        assert!(pi.lamp());
+        assert_eq!(pi.setpoint(), -10);
+        assert_eq!(pi.flags(), 0x83);
+
+        let forced: Vec<_> = forces
+            .iter()
+            .map(|(tag, value)| (tag.name(), value))
+            .collect();
+        assert_eq!(
+            forced,
+            [("lamp", Value::Bool(true)), ("setpoint", Value::Int(-10))]
+        );
+
+        forces.force("flags", Value::Byte(0x00)).unwrap();
+        forces.apply_forces(pi.as_slice_mut());
+        assert_eq!(pi.flags(), 0x00);
+        assert_eq!(forces.iter().count(), 4);
+
+        forces.unforce("valve").unwrap();
+        assert!(!forces.is_forced("flags"));
+        forces.unforce_address("%QW2".parse().unwrap()).unwrap();
+        forces.unforce("flags").unwrap();
+        assert!(forces.is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let mut forces = Forces::<4>::new::<TestPiOutputs>();
+        assert_eq!(
+            forces.force("pump", Value::Bool(true)),
+            Err(AccessError::UnknownTag)
+        );
+        assert_eq!(
+            forces.force_address("%QX1.0".parse().unwrap(), Value::Bool(true)),
+            Err(AccessError::UnknownTag)
+        );
+        assert_eq!(
+            forces.force("setpoint", Value::Word(10)),
+            Err(AccessError::TypeMismatch)
+        );
+        assert!(forces.is_empty());
+    }
+
+    #[test]
+    fn force_bit_fields() {
+        let mut forces = Forces::<2>::new::<TestPiBitFields>();
+        forces.force("mode", Value::Word(7)).unwrap();
+        assert!(forces.is_forced("mode"));
+        assert!(!forces.is_forced("state"));
+
+        let mut buf = [0x30, 0x02];
+        forces.apply_forces(&mut buf);
+        assert_eq!(buf, [0x30, 0x07]);
+    }
+
+    #[test]
+    fn force_word_bits() {
+        let mut forces = Forces::<8>::new::<TestPiWordBits>();
+        forces.force("fault", Value::Bool(true)).unwrap();
+        forces
+            .force_address("%MD4.31".parse().unwrap(), Value::Bool(false))
+            .unwrap();
+        assert!(forces.is_forced("ready"));
+        assert!(!forces.is_forced("halt"));
+
+        let mut buf = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff];
+        forces.apply_forces(&mut buf);
+        assert_eq!(buf, [0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00, 0x7f]);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::tag_info::TagInfo;
+use crate::value::Value;
+
+/// Common interface of all process images generated by [`process_image!{}`][`crate::process_image`]
+/// and [`process_image_owned!{}`][`crate::process_image_owned`].
+///
+/// This allows writing code that is generic over the process image type, for example to log any
+/// process image:
+///
+/// ```
+/// use process_image::ProcessImage;
+///
+/// fn dump<P: ProcessImage>(pi: &P) {
+///     println!("{} bytes: {:02x?}", P::SIZE, pi.as_bytes());
+/// }
+///
+/// process_image::process_image! {
+///     pub struct PiInputs: 4 in I {
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// dump(&PiInputs::from(&[0x00; 4]));
+/// ```
+///
+/// All structs generated from one definition share the same [`Ref`][`ProcessImage::Ref`] and
+/// [`Mut`][`ProcessImage::Mut`] types.  They are the generated structs which borrow the buffer
+/// immutably and mutably and provide the tag accessor methods.  If the definition has no such
+/// struct (e.g. `struct mut PiOutputs` or an owned process image), a hidden one is generated.  The
+/// hidden mutable struct of a definition without `mut` only provides immutable tag accessors and
+/// does not implement [`ProcessImageMut`], so there is no mutable access to e.g. an input image:
+///
+/// ```compile_fail
+/// use process_image::{ProcessImage, ProcessImageMut};
+///
+/// process_image::process_image! {
+///     pub struct PiInputs: 4 in I {
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let mut buf = [0x00; 4];
+/// let mut pi = <PiInputs as ProcessImage>::Mut::try_from(&mut buf[..]).unwrap();
+/// pi.as_bytes_mut().fill(0xff);
+/// ```
+pub trait ProcessImage {
+    /// Size of the process image in bytes.
+    const SIZE: usize;
+
+    /// Metadata of all tags of the process image, in the order of the definition.
+    const TAGS: &'static [TagInfo];
+
+    /// Type for immutable access to the tags of a borrowed buffer.
+    type Ref<'a>: TryFrom<&'a [u8]>;
+
+    /// Type for mutable access to the tags of a borrowed buffer.
+    type Mut<'a>: TryFrom<&'a mut [u8]>;
+
+    /// The raw data of the process image.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Call `f` with the metadata and current value of each tag.
+    ///
+    /// ```
+    /// use process_image::{ProcessImage, Value};
+    ///
+    /// process_image::process_image! {
+    ///     pub struct PiInputs: 4 in I {
+    ///         pub door_closed: (X, 0, 3),
+    ///         pub speed: (W, 2),
+    ///     }
+    /// }
+    ///
+    /// let pi = PiInputs::from(&[0x08, 0x00, 0x05, 0xdc]);
+    /// let mut values = Vec::new();
+    /// pi.visit_tags(|tag, value| values.push((tag.name(), value)));
+    /// assert_eq!(
+    ///     values,
+    ///     [("door_closed", Value::Bool(true)), ("speed", Value::Word(1500))],
+    /// );
+    /// ```
+    fn visit_tags(&self, mut f: impl FnMut(&'static TagInfo, Value)) {
+        let buf = self.as_bytes();
+        for tag in Self::TAGS {
+            f(tag, tag.read(buf));
+        }
+    }
+}
+
+/// Common interface of all process images with mutable access to their data.
+///
+/// This is implemented by the mutable structs generated by [`process_image!{}`][`crate::process_image`]
+/// for definitions with `mut` and by all owned process images.  Drivers can use it to fill any process image:
+///
+/// ```
+/// use process_image::ProcessImageMut;
+///
+/// fn receive<P: ProcessImageMut>(pi: &mut P, frame: &[u8]) {
+///     pi.as_bytes_mut().copy_from_slice(&frame[..P::SIZE]);
+/// }
+///
+/// process_image::process_image_owned! {
+///     pub struct PiInputs, mut PiInputsMut: 4 {
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let mut pi = PiInputs::new_zeroed();
+/// receive(&mut pi, &[0x00, 0x00, 0x05, 0xdc, 0xff]);
+/// assert_eq!(pi.speed(), 1500);
+/// ```
+pub trait ProcessImageMut: ProcessImage {
+    /// The raw data of the process image, mutably.
+    fn as_bytes_mut(&mut self) -> &mut [u8];
+}
@@ -20,6 +20,7 @@
 //! %IX100.4 = bit 4 in byte address 100
 //! %IB8     = byte at address 8
 //! %IW16    = word starting at address 16
+//! %IW16.12 = bit 12 in the word starting at address 16
 //! ```
 //!
 //! | Specifier | Rust | Type |
@@ -29,6 +30,16 @@
 //! | `W` | `u16` | Word |
 //! | `D` | `u32` | Double Word |
 //! | `L` | `u64` | Long Word |
+//! | `SINT` | `i8` | Short Integer |
+//! | `INT` | `i16` | Integer |
+//! | `DINT` | `i32` | Double Integer |
+//! | `LINT` | `i64` | Long Integer |
+//! | `REAL` | `f32` | Real (IEEE 754 single precision) |
+//! | `LREAL` | `f64` | Long Real (IEEE 754 double precision) |
+//!
+//! The signed integer and floating point types are not part of the IEC 61131-3 address syntax.
+//! The signed integers reuse the storage of `B`, `W`, `D`, and `L` respectively but interpret the
+//! data in two's complement.  `REAL` and `LREAL` reuse the storage of `D` and `L`.
 //!
 //! The meaning of each bit and byte is defined by the hardware configuration of the PLC and the
 //! equipment connected to it.  Usually the input and output addresses are also referenced in
@@ -60,62 +71,331 @@
 //! let w: u16   = process_image::tag!(&pii, W, 2);    // %IW2
 //! let d: u32   = process_image::tag!(&pii, D, 4);    // %ID4
 //! let l: u64   = process_image::tag!(&pii, L, 8);    // %IL8
+//!
+//! let si: i8   = process_image::tag!(&pii, SINT, 1); // %IB1
+//! let i: i16   = process_image::tag!(&pii, INT, 2);  // %IW2
+//! let di: i32  = process_image::tag!(&pii, DINT, 4); // %ID4
+//! let li: i64  = process_image::tag!(&pii, LINT, 8); // %IL8
+//!
+//! let r: f32   = process_image::tag!(&pii, REAL, 4);  // %ID4
+//! let lr: f64  = process_image::tag!(&pii, LREAL, 8); // %IL8
+//! ```
+//!
+//! Alternatively, the IEC 61131-3 address can be given as a string, which is parsed at compile
+//! time:
+//!
+//! ```
+//! # let pii = [0x00; 16];
+//! let b: bool = process_image::tag!(&pii, "%IX0.1");
+//! let w: u16  = process_image::tag!(&pii, "%IW2");
+//! ```
+//!
+//! When addresses are only known at runtime, e.g. because they come from a configuration file,
+//! the [`Address`] type can be used instead.  It parses and prints the IEC 61131-3 syntax and
+//! reads or writes a [`Value`] in a buffer:
+//!
+//! ```
+//! use process_image::{Address, Value};
+//!
+//! let pii = [0x00, 0x10, 0xca, 0xfe];
+//!
+//! let addr: Address = "%IW2".parse().unwrap();
+//! assert_eq!(addr.read(&pii), Ok(Value::Word(0xcafe)));
 //! ```
 //!
+//! With the `alloc` crate feature, whole tag lists can be loaded at runtime into a `TagTable`
+//! which provides access to the tags by their name.
+//!
+//! With the `serde` crate feature, process images implement `Serialize` as a map from tag name to
+//! value.  Owned process images also implement `Deserialize`.
+//!
 //! # Endianness
-//! All data is accessed in big-endian (MSB-first) byte order.
+//! By default, all data is accessed in big-endian (MSB-first) byte order.  A different byte order
+//! can be selected by appending `@ little_endian` (or `@ big_endian`) to the type specifier of a
+//! tag:
+//!
+//! ```
+//! let pii = [0x34, 0x12, 0x00, 0x00];
+//!
+//! let be: u16 = process_image::tag!(&pii, W, 0);                 // 0x3412
+//! let le: u16 = process_image::tag!(&pii, W @ little_endian, 0); // 0x1234
+//! # assert_eq!(be, 0x3412);
+//! # assert_eq!(le, 0x1234);
+//! ```
+//!
+//! For [`process_image!{}`][`process_image`] and [`process_image_owned!{}`][`process_image_owned`],
+//! the byte order is selected for the whole process image after its size.  See the documentation
+//! of these macros for details.
+//!
+//! Devices speaking Modbus often transfer 32-bit and 64-bit values as multiple 16-bit registers in
+//! a mixed byte order.  To describe such layouts, the byte order can also be given in the
+//! conventional Modbus notation, shown here for the double word `0xAABBCCDD`:
+//!
+//! | Byte Order | Bytes in Process Image | Marker Type |
+//! | --- | --- | --- |
+//! | `big_endian` or `ABCD` | `AA BB CC DD` | [`BigEndian`] |
+//! | `little_endian` or `DCBA` | `DD CC BB AA` | [`LittleEndian`] |
+//! | `CDAB` | `CC DD AA BB` | [`WordSwapped`] |
+//! | `BADC` | `BB AA DD CC` | [`ByteSwapped`] |
 //!
 //! # Alignment
 //! By default, addresses of _words, double words,_ and _long words_ must be aligned to the size of
-//! the data type.  Unaligned addresses will lead to a panic at runtime.
+//! the data type.  Unaligned addresses in [`tag!()`][`tag`] and [`tag_mut!()`][`tag_mut`] will lead
+//! to a panic at runtime.  Tags in a [`process_image!{}`][`process_image`] are checked at compile
+//! time instead.
 //!
 //! | Type | Alignment |
 //! | --- | --- |
 //! | Boolean Bit | 1 (none) |
-//! | Byte | 1 (none) |
-//! | Word | 2 |
-//! | Double Word | 4 |
-//! | Long Word | 8 |
+//! | Byte, Short Integer | 1 (none) |
+//! | Word, Integer | 2 |
+//! | Double Word, Double Integer, Real | 4 |
+//! | Long Word, Long Integer, Long Real | 8 |
 //!
 //! However, there are some situations where this behavior is undesired.  In some control systems,
-//! the process image is constructed without alignment.  To cater to such uses,
-//! alignment-enforcement can be disabled globally with the `allow_unaligned_tags` crate feature.
-//! Do note that this will disable alignment globally for all users of `process-image` in the
-//! crate dependency-graph.
+//! the process image is constructed without alignment.  To cater to such uses, the maximum
+//! alignment can be lowered with an `align = N` option, both for individual
+//! [`tag!()`][`tag`]/[`tag_mut!()`][`tag_mut`] calls and for whole process images.  A tag then
+//! needs to be aligned to the smaller of its own size and `N`.  Thus, `align = 1` allows arbitrary
+//! addresses:
+//!
+//! ```
+//! let pi = [0x00, 0xde, 0xad, 0xbe, 0xef, 0x00];
+//!
+//! let w: u16 = process_image::tag!(&pi, W, 1, align = 1);
+//! let d: u32 = process_image::tag!(&pi, D, 2, align = 2);
+//! # assert_eq!(w, 0xdead);
+//! # assert_eq!(d, 0xadbeef00);
+//!
+//! process_image::process_image! {
+//!     pub struct PiPacked: 6, align = 1 {
+//!         pub status: (B, 0),
+//!         pub counter: (D, 1),
+//!     }
+//! }
+//! # assert_eq!(PiPacked::from(&pi).counter(), 0xdeadbeef);
+//! ```
 //!
-//! In the future, alignment-enforcement might be dropped entirely.
+//! `N` must be 1, 2, 4, or 8.  Other values fail the build:
+//!
+//! ```compile_fail
+//! let pi = [0x00, 0xde, 0xad, 0xbe, 0xef, 0x00];
+//! let w: u16 = process_image::tag!(&pi, W, 1, align = 3);
+//! ```
+//!
+//! The deprecated `allow_unaligned_tags` crate feature changes the default of
+//! [`tag!()`][`tag`] and [`tag_mut!()`][`tag_mut`] to `align = 1`.  Do note that this affects all
+//! users of `process-image` in the crate dependency-graph.  Process images are not affected by it
+//! and always need the `align` option to relax the alignment-enforcement.
 #![cfg_attr(not(test), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 mod access;
-pub use access::{BitMut, DWordMut, LWordMut, WordMut};
+mod address;
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+mod byte_order;
+#[doc(hidden)]
+pub mod cell;
+#[doc(hidden)]
+pub mod check;
+mod diff;
+mod forces;
+mod image;
+#[doc(hidden)]
+pub mod serialize;
+#[cfg(target_has_atomic = "ptr")]
+mod shared_image;
+mod tag_info;
+#[cfg(feature = "alloc")]
+mod tag_table;
+#[cfg(target_has_atomic = "8")]
+mod triple_buffer;
+mod value;
+mod volatile;
+pub use access::{
+    BitField, BitFieldMut, BitMut, DIntMut, DWordMut, IntMut, LIntMut, LRealMut, LWordMut, RealMut,
+    SIntMut, WordMut,
+};
+pub use address::{AccessError, Address, Area, ParseAddressError, Width};
+#[cfg(target_has_atomic = "64")]
+pub use atomic::{
+    AtomicBit, AtomicBitField, AtomicBuffer, AtomicByte, AtomicDWord, AtomicLWord, AtomicWord,
+};
+pub use byte_order::{BigEndian, ByteOrder, ByteSwapped, LittleEndian, WordSwapped};
+pub use diff::{Diff, TagChange};
+pub use forces::Forces;
+pub use image::{ProcessImage, ProcessImageMut};
+#[cfg(target_has_atomic = "ptr")]
+pub use shared_image::SharedImage;
+pub use tag_info::{DataType, TagInfo};
+#[cfg(feature = "alloc")]
+pub use tag_table::{TagTable, TagTableError};
+#[cfg(target_has_atomic = "8")]
+pub use triple_buffer::{Consumer, Producer, TripleBuffer};
+pub use value::Value;
+pub use volatile::{
+    VolatileBitFieldMut, VolatileBitMut, VolatileBuffer, VolatileMut, VolatileValue,
+};
+
+#[doc(hidden)]
+pub use address::DebugTag;
+#[doc(hidden)]
+pub use process_image_macros::{iec_tag, iec_tags, paste_idents};
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+
+#[doc(hidden)]
+pub const DEFAULT_ALIGNMENT: usize = 8;
+
+// Only `tag!()` and `tag_mut!()` honor the deprecated `allow_unaligned_tags` feature.  Process
+// images always default to `DEFAULT_ALIGNMENT`.
+#[doc(hidden)]
+pub const DEFAULT_TAG_ALIGNMENT: usize = if cfg!(feature = "allow_unaligned_tags") {
+    1
+} else {
+    DEFAULT_ALIGNMENT
+};
+
+#[doc(hidden)]
+#[inline(always)]
+pub const fn required_alignment(size: usize, align: usize) -> usize {
+    if size < align {
+        size
+    } else {
+        align
+    }
+}
 
-#[cfg(feature = "allow_unaligned_tags")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! alignment_assert {
-    ($align:literal, $addr:literal) => {};
+    (2, $addr:expr) => {
+        $crate::alignment_assert!(2, $addr, $crate::DEFAULT_TAG_ALIGNMENT)
+    };
+    (4, $addr:expr) => {
+        $crate::alignment_assert!(4, $addr, $crate::DEFAULT_TAG_ALIGNMENT)
+    };
+    (8, $addr:expr) => {
+        $crate::alignment_assert!(8, $addr, $crate::DEFAULT_TAG_ALIGNMENT)
+    };
+    (2, $addr:expr, $align:expr) => {
+        $crate::alignment_assert!(@check $align);
+        let alignment = $crate::required_alignment(2, $align);
+        assert!(
+            $addr % alignment == 0,
+            "Word address must be divisible by {}",
+            alignment
+        );
+    };
+    (4, $addr:expr, $align:expr) => {
+        $crate::alignment_assert!(@check $align);
+        let alignment = $crate::required_alignment(4, $align);
+        assert!(
+            $addr % alignment == 0,
+            "Double word address must be divisible by {}",
+            alignment
+        );
+    };
+    (8, $addr:expr, $align:expr) => {
+        $crate::alignment_assert!(@check $align);
+        let alignment = $crate::required_alignment(8, $align);
+        assert!(
+            $addr % alignment == 0,
+            "Long word address must be divisible by {}",
+            alignment
+        );
+    };
+    (@check $align:expr) => {
+        const {
+            assert!(
+                $align == 1 || $align == 2 || $align == 4 || $align == 8,
+                "Alignment must be 1, 2, 4, or 8",
+            );
+        }
+    };
 }
 
-#[cfg(not(feature = "allow_unaligned_tags"))]
 #[doc(hidden)]
 #[macro_export]
-macro_rules! alignment_assert {
-    (2, $addr:literal) => {
-        assert!($addr % 2 == 0, "Word address must be divisible by 2");
+macro_rules! bit_field_assert {
+    ($bits:literal, $low:literal, $high:literal) => {
+        const {
+            assert!(
+                $low <= $high && $high < $bits,
+                ::core::concat!("Bit field must be within the ", $bits, " bits of the tag"),
+            );
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! bit_index_assert {
+    ($bits:literal, $bit:literal) => {
+        const {
+            assert!(
+                $bit < $bits,
+                ::core::concat!("Bit index must be within the ", $bits, " bits of the tag"),
+            );
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! edge_method {
+    ($vis:vis, $name:ident, $kind:ident $(@ $order:ident)?, $byte:literal, $bit:literal) => {
+        $crate::edge_method!(@bit $vis, $name);
     };
-    (4, $addr:literal) => {
-        assert!($addr % 4 == 0, "Double word address must be divisible by 4");
+    ($vis:vis, $name:ident, $byte:literal, $bit:literal) => {
+        $crate::edge_method!(@bit $vis, $name);
     };
-    (8, $addr:literal) => {
-        assert!($addr % 8 == 0, "Long word address must be divisible by 8");
+    (@bit $vis:vis, $name:ident) => {
+        $crate::paste_idents! {
+            #[doc = ::core::concat!(
+                "Whether `", ::core::stringify!($name), "` changed from `false` to `true`."
+            )]
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<$name _rising>](&self) -> bool {
+                !self.previous.$name() && self.current.$name()
+            }
+
+            #[doc = ::core::concat!(
+                "Whether `", ::core::stringify!($name), "` changed from `true` to `false`."
+            )]
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<$name _falling>](&self) -> bool {
+                self.previous.$name() && !self.current.$name()
+            }
+        }
     };
+    ($vis:vis, $name:ident, $($tag:tt)+) => {};
 }
 
 /// Read tag values from a process image with absolute addressing.
 ///
-/// Addresses must be aligned to the size of the datatype (i.e. word=2, dword=4, lword=8).
+/// Addresses must be aligned to the size of the datatype (i.e. word=2, dword=4, lword=8).  The
+/// alignment requirement can be relaxed by appending `align = N` (see the [crate-level
+/// documentation](crate#alignment)).
 ///
-/// Multi-byte datatypes are always accessed in big-endian order.
+/// Multi-byte datatypes are accessed in big-endian order unless a different byte order is
+/// selected with `@ little_endian`.
+///
+/// Appending a range of bits like `4..=6` to a byte or word reads only these bits, shifted down to
+/// bit 0.
+///
+/// Bits of words, double words, and long words are addressed by their index in the word, e.g.
+/// `W, 2, 12` for `%MW2.12`.  Which byte holds the bit depends on the byte order.  The bit index
+/// must be a literal, indices beyond the width of the word fail the build.
+///
+/// Instead of the type specifier and address, an IEC 61131-3 address string like `"%IW16"` can be
+/// given.  It is parsed at compile time and malformed addresses fail the build.
 ///
 /// # Example
 /// ```
@@ -124,6 +404,7 @@ macro_rules! alignment_assert {
 /// // Bit access
 /// let b1: bool = process_image::tag!(&pi, X, 0, 0);   // %MX0.0
 /// let b2: bool = process_image::tag!(&pi, 0, 1);      // %MX0.1
+/// let b3: bool = process_image::tag!(&pi, W, 2, 12);  // %MW2.12
 ///
 /// // Byte access
 /// let by: u8 = process_image::tag!(&pi, B, 1);        // %MB1
@@ -136,9 +417,73 @@ macro_rules! alignment_assert {
 ///
 /// // Long word access
 /// let l: u64 = process_image::tag!(&pi, L, 8);        // %ML8
+///
+/// // Signed integer access
+/// let si: i8 = process_image::tag!(&pi, SINT, 1);     // %MB1
+/// let i: i16 = process_image::tag!(&pi, INT, 2);      // %MW2
+/// let di: i32 = process_image::tag!(&pi, DINT, 4);    // %MD4
+/// let li: i64 = process_image::tag!(&pi, LINT, 8);    // %ML8
+///
+/// // Floating point access
+/// let r: f32 = process_image::tag!(&pi, REAL, 4);     // %MD4
+/// let lr: f64 = process_image::tag!(&pi, LREAL, 8);   // %ML8
+///
+/// // Explicit byte order
+/// let w: u16 = process_image::tag!(&pi, W @ little_endian, 2);
+/// let r: f32 = process_image::tag!(&pi, REAL @ big_endian, 4);
+///
+/// // Unaligned access
+/// let w: u16 = process_image::tag!(&pi, W, 3, align = 1);
+/// let d: u32 = process_image::tag!(&pi, D @ little_endian, 6, align = 2);
+///
+/// // Bit fields
+/// let state: u16 = process_image::tag!(&pi, W, 2, 0..=3); // %MW2, bits 0 to 3
+/// let mode: u8 = process_image::tag!(&pi, B, 1, 5..=7);   // %MB1, bits 5 to 7
+///
+/// // IEC 61131-3 address strings
+/// let b: bool = process_image::tag!(&pi, "%MX0.0");
+/// let w: u16 = process_image::tag!(&pi, "%MW2");
+/// let d: u32 = process_image::tag!(&pi, "%MD3", align = 1);
 /// ```
 #[macro_export]
 macro_rules! tag {
+    ($buf:expr, B, $addr:expr, $low:literal ..= $high:literal) => {{
+        $crate::bit_field_assert!(8, $low, $high);
+        $crate::BitField::bits($crate::tag!($buf, B, $addr), $low, $high)
+    }};
+    (
+        $buf:expr, W $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        $crate::bit_field_assert!(16, $low, $high);
+        $crate::BitField::bits(
+            $crate::tag!($buf, W $(@ $order)?, $addr $(, align = $align)?),
+            $low,
+            $high,
+        )
+    }};
+    (
+        $buf:expr, D $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        $crate::bit_field_assert!(32, $low, $high);
+        $crate::BitField::bits(
+            $crate::tag!($buf, D $(@ $order)?, $addr $(, align = $align)?),
+            $low,
+            $high,
+        )
+    }};
+    (
+        $buf:expr, L $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        $crate::bit_field_assert!(64, $low, $high);
+        $crate::BitField::bits(
+            $crate::tag!($buf, L $(@ $order)?, $addr $(, align = $align)?),
+            $low,
+            $high,
+        )
+    }};
     ($buf:expr, X, $addr1:expr, $addr2:expr) => {{
         let buffer: &[u8] = $buf;
         buffer[$addr1] & (1 << $addr2) != 0
@@ -147,21 +492,93 @@ macro_rules! tag {
         let buffer: &[u8] = $buf;
         buffer[$addr]
     }};
-    ($buf:expr, W, $addr:expr) => {{
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u16(
+            buffer[$addr..$addr + 2].try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u32(
+            buffer[$addr..$addr + 4].try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
         let buffer: &[u8] = $buf;
-        $crate::alignment_assert!(2, $addr);
-        u16::from_be_bytes(buffer[$addr..$addr + 2].try_into().unwrap())
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u64(
+            buffer[$addr..$addr + 8].try_into().unwrap(),
+        )
     }};
-    ($buf:expr, D, $addr:expr) => {{
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(16, $bit);
         let buffer: &[u8] = $buf;
-        $crate::alignment_assert!(4, $addr);
-        u32::from_be_bytes(buffer[$addr..$addr + 4].try_into().unwrap())
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        let (byte, bit) =
+            <u16 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        buffer[$addr + byte] & (1 << bit) != 0
     }};
-    ($buf:expr, L, $addr:expr) => {{
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(32, $bit);
         let buffer: &[u8] = $buf;
-        $crate::alignment_assert!(8, $addr);
-        u64::from_be_bytes(buffer[$addr..$addr + 8].try_into().unwrap())
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        let (byte, bit) =
+            <u32 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        buffer[$addr + byte] & (1 << bit) != 0
     }};
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(64, $bit);
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        let (byte, bit) =
+            <u64 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        buffer[$addr + byte] & (1 << bit) != 0
+    }};
+    ($buf:expr, SINT, $addr:expr) => {{
+        let buffer: &[u8] = $buf;
+        buffer[$addr] as i8
+    }};
+    ($buf:expr, INT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u16(
+            buffer[$addr..$addr + 2].try_into().unwrap(),
+        ) as i16
+    }};
+    ($buf:expr, DINT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u32(
+            buffer[$addr..$addr + 4].try_into().unwrap(),
+        ) as i32
+    }};
+    ($buf:expr, LINT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        <$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u64(
+            buffer[$addr..$addr + 8].try_into().unwrap(),
+        ) as i64
+    }};
+    ($buf:expr, REAL $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        f32::from_bits(<$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u32(
+            buffer[$addr..$addr + 4].try_into().unwrap(),
+        ))
+    }};
+    ($buf:expr, LREAL $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &[u8] = $buf;
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        f64::from_bits(<$crate::byte_order!($($order)?) as $crate::ByteOrder>::read_u64(
+            buffer[$addr..$addr + 8].try_into().unwrap(),
+        ))
+    }};
+    ($buf:expr, $addr:literal $(, align = $align:literal)?) => {
+        $crate::iec_tag!($crate::tag ($buf) $addr $(, align = $align)?)
+    };
     ($buf:expr, $addr1:expr, $addr2:expr) => {{
         let buffer: &[u8] = $buf;
         buffer[$addr1] & (1 << $addr2) != 0
@@ -170,9 +587,21 @@ macro_rules! tag {
 
 /// Mutable access to tag values from a process image with absolute addressing.
 ///
-/// Addresses must be aligned to the size of the datatype (i.e. word=2, dword=4, lword=8).
+/// Addresses must be aligned to the size of the datatype (i.e. word=2, dword=4, lword=8).  The
+/// alignment requirement can be relaxed by appending `align = N` (see the [crate-level
+/// documentation](crate#alignment)).
+///
+/// Multi-byte datatypes are accessed in big-endian order unless a different byte order is
+/// selected with `@ little_endian`.
 ///
-/// Multi-byte datatypes are always accessed in big-endian order.
+/// Appending a range of bits like `4..=6` to a byte or word returns a [`BitFieldMut`] which only
+/// writes these bits and leaves the rest of the byte or word untouched.
+///
+/// Bits of words, double words, and long words are addressed by their index in the word, e.g.
+/// `W, 2, 12` for `%MW2.12`.  Which byte holds the bit depends on the byte order.
+///
+/// Instead of the type specifier and address, an IEC 61131-3 address string like `"%QW16"` can be
+/// given.  It is parsed at compile time and malformed addresses fail the build.
 ///
 /// # Example
 /// ```
@@ -181,6 +610,7 @@ macro_rules! tag {
 /// // Bit access
 /// *process_image::tag_mut!(&mut pi, X, 0, 0) = true;  // %MX0.0
 /// *process_image::tag_mut!(&mut pi, 0, 1) = true;     // %MX0.1
+/// *process_image::tag_mut!(&mut pi, W, 2, 12) = true; // %MW2.12
 ///
 /// // Byte access
 /// *process_image::tag_mut!(&mut pi, B, 1) = 42u8;     // %MB1
@@ -193,9 +623,79 @@ macro_rules! tag {
 ///
 /// // Long word access
 /// *process_image::tag_mut!(&mut pi, L, 8) = 1;        // %ML8
+///
+/// // Signed integer access
+/// *process_image::tag_mut!(&mut pi, SINT, 1) = -42;   // %MB1
+/// *process_image::tag_mut!(&mut pi, INT, 2) = -1337;  // %MW2
+/// *process_image::tag_mut!(&mut pi, DINT, 4) = -1;    // %MD4
+/// *process_image::tag_mut!(&mut pi, LINT, 8) = i64::MIN; // %ML8
+///
+/// // Floating point access
+/// *process_image::tag_mut!(&mut pi, REAL, 4) = 3.1415; // %MD4
+/// *process_image::tag_mut!(&mut pi, LREAL, 8) = -0.5; // %ML8
+///
+/// // Explicit byte order
+/// *process_image::tag_mut!(&mut pi, W @ little_endian, 2) = 0x1234;
+/// *process_image::tag_mut!(&mut pi, REAL @ big_endian, 4) = 1.0;
+///
+/// // Unaligned access
+/// *process_image::tag_mut!(&mut pi, W, 3, align = 1) = 0xcafe;
+/// *process_image::tag_mut!(&mut pi, D @ little_endian, 6, align = 2) = 0xc0ffee;
+///
+/// // Bit fields, only the bits of the field are written
+/// *process_image::tag_mut!(&mut pi, W, 2, 0..=3) = 0x5;  // %MW2, bits 0 to 3
+/// *process_image::tag_mut!(&mut pi, B, 1, 5..=7) = 0x2;  // %MB1, bits 5 to 7
+///
+/// // IEC 61131-3 address strings
+/// *process_image::tag_mut!(&mut pi, "%MX0.0") = true;
+/// *process_image::tag_mut!(&mut pi, "%MW2") = 1337u16;
 /// ```
 #[macro_export]
 macro_rules! tag_mut {
+    ($buf:expr, B, $addr:expr, $low:literal ..= $high:literal) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::bit_field_assert!(8, $low, $high);
+        $crate::BitFieldMut::<u8>::new(&mut buffer[$addr..$addr + 1], $low, $high)
+    }};
+    (
+        $buf:expr, W $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::bit_field_assert!(16, $low, $high);
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        $crate::BitFieldMut::<u16, $crate::byte_order!($($order)?)>::with_byte_order(
+            &mut buffer[$addr..$addr + 2],
+            $low,
+            $high,
+        )
+    }};
+    (
+        $buf:expr, D $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::bit_field_assert!(32, $low, $high);
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        $crate::BitFieldMut::<u32, $crate::byte_order!($($order)?)>::with_byte_order(
+            &mut buffer[$addr..$addr + 4],
+            $low,
+            $high,
+        )
+    }};
+    (
+        $buf:expr, L $(@ $order:ident)?, $addr:expr, $low:literal ..= $high:literal
+        $(, align = $align:literal)?
+    ) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::bit_field_assert!(64, $low, $high);
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        $crate::BitFieldMut::<u64, $crate::byte_order!($($order)?)>::with_byte_order(
+            &mut buffer[$addr..$addr + 8],
+            $low,
+            $high,
+        )
+    }};
     ($buf:expr, X, $addr1:expr, $addr2:expr) => {{
         let buffer: &mut [u8] = $buf;
         $crate::BitMut::new(&mut buffer[$addr1], $addr2)
@@ -204,103 +704,767 @@ macro_rules! tag_mut {
         let buffer: &mut [u8] = $buf;
         &mut buffer[$addr]
     }};
-    ($buf:expr, W, $addr:expr) => {{
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        $crate::WordMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 2]).try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        $crate::DWordMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 4]).try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        $crate::LWordMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 8]).try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(16, $bit);
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        let (byte, bit) =
+            <u16 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::BitMut::new(&mut buffer[$addr + byte], bit)
+    }};
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(32, $bit);
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        let (byte, bit) =
+            <u32 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::BitMut::new(&mut buffer[$addr + byte], bit)
+    }};
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr, $bit:literal $(, align = $align:literal)?) => {{
+        $crate::bit_index_assert!(64, $bit);
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        let (byte, bit) =
+            <u64 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::BitMut::new(&mut buffer[$addr + byte], bit)
+    }};
+    ($buf:expr, SINT, $addr:expr) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::SIntMut::new(&mut buffer[$addr])
+    }};
+    ($buf:expr, INT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(2, $addr $(, $align)?);
+        $crate::IntMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 2]).try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, DINT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
+        let buffer: &mut [u8] = $buf;
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        $crate::DIntMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 4]).try_into().unwrap(),
+        )
+    }};
+    ($buf:expr, LINT $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
         let buffer: &mut [u8] = $buf;
-        $crate::alignment_assert!(2, $addr);
-        $crate::WordMut::new((&mut buffer[$addr..$addr + 2]).try_into().unwrap())
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        $crate::LIntMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 8]).try_into().unwrap(),
+        )
     }};
-    ($buf:expr, D, $addr:expr) => {{
+    ($buf:expr, REAL $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
         let buffer: &mut [u8] = $buf;
-        $crate::alignment_assert!(4, $addr);
-        $crate::DWordMut::new((&mut buffer[$addr..$addr + 4]).try_into().unwrap())
+        $crate::alignment_assert!(4, $addr $(, $align)?);
+        $crate::RealMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 4]).try_into().unwrap(),
+        )
     }};
-    ($buf:expr, L, $addr:expr) => {{
+    ($buf:expr, LREAL $(@ $order:ident)?, $addr:expr $(, align = $align:literal)?) => {{
         let buffer: &mut [u8] = $buf;
-        $crate::alignment_assert!(8, $addr);
-        $crate::LWordMut::new((&mut buffer[$addr..$addr + 8]).try_into().unwrap())
+        $crate::alignment_assert!(8, $addr $(, $align)?);
+        $crate::LRealMut::<$crate::byte_order!($($order)?)>::with_byte_order(
+            (&mut buffer[$addr..$addr + 8]).try_into().unwrap(),
+        )
     }};
+    ($buf:expr, $addr:literal $(, align = $align:literal)?) => {
+        $crate::iec_tag!($crate::tag_mut ($buf) $addr $(, align = $align)?)
+    };
     ($buf:expr, $addr1:expr, $addr2:expr) => {{
         let buffer: &mut [u8] = $buf;
         $crate::BitMut::new(&mut buffer[$addr1], $addr2)
     }};
 }
 
+/// Atomic access to tag values from a process image shared between threads.
+///
+/// The process image is an [`AtomicBuffer`].  Instead of a value, an accessor is returned which
+/// loads and stores the tag with atomic operations on the aligned 64-bit word containing it:
+///
+/// - Bits ([`AtomicBit`]) are written with `fetch_or()`/`fetch_and()`, so concurrent writes to
+///   other bits of the same byte are not lost like with [`BitMut`].
+/// - Bytes ([`AtomicByte`]), words ([`AtomicWord`]), double words ([`AtomicDWord`]), and long
+///   words ([`AtomicLWord`]) are loaded and stored with a single atomic operation.  Signed and
+///   floating point tags are accessed through the accessor of their width.
+///
+/// Multi-byte tags must be aligned to their size, otherwise creating the accessor panics.
+///
+/// # Example
+/// ```
+/// use core::sync::atomic::Ordering;
+/// use process_image::AtomicBuffer;
+///
+/// let pi = AtomicBuffer::<16>::new();
+///
+/// // Bit access
+/// process_image::tag_atomic!(&pi, X, 0, 0).store(true, Ordering::Relaxed);  // %MX0.0
+/// process_image::tag_atomic!(&pi, 0, 1).store(true, Ordering::Relaxed);     // %MX0.1
+///
+/// // Byte access
+/// process_image::tag_atomic!(&pi, B, 1).store(42, Ordering::Relaxed);       // %MB1
+///
+/// // Word, double word, and long word access
+/// process_image::tag_atomic!(&pi, W, 2).store(1337, Ordering::Relaxed);    // %MW2
+/// process_image::tag_atomic!(&pi, D @ little_endian, 4).store(1, Ordering::Relaxed);
+/// let lr = f64::from_bits(process_image::tag_atomic!(&pi, LREAL, 8).load(Ordering::Relaxed));
+///
+/// // IEC 61131-3 address strings
+/// let b: bool = process_image::tag_atomic!(&pi, "%MX0.1").load(Ordering::Relaxed);
+/// # assert!(b);
+/// # assert_eq!(pi.load()[..8], [0x03, 42, 0x05, 0x39, 0x01, 0x00, 0x00, 0x00]);
+/// # assert_eq!(lr, 0.0);
+/// ```
+#[macro_export]
+macro_rules! tag_atomic {
+    ($buf:expr, X, $addr1:expr, $addr2:expr) => {
+        $crate::tag_atomic!($buf, $addr1, $addr2)
+    };
+    ($buf:expr, B, $addr:expr) => {
+        $crate::AtomicBuffer::byte($buf, $addr)
+    };
+    ($buf:expr, SINT, $addr:expr) => {
+        $crate::tag_atomic!($buf, B, $addr)
+    };
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr) => {
+        $crate::AtomicBuffer::word::<$crate::byte_order!($($order)?)>($buf, $addr)
+    };
+    ($buf:expr, INT $(@ $order:ident)?, $addr:expr) => {
+        $crate::tag_atomic!($buf, W $(@ $order)?, $addr)
+    };
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr) => {
+        $crate::AtomicBuffer::dword::<$crate::byte_order!($($order)?)>($buf, $addr)
+    };
+    ($buf:expr, DINT $(@ $order:ident)?, $addr:expr) => {
+        $crate::tag_atomic!($buf, D $(@ $order)?, $addr)
+    };
+    ($buf:expr, REAL $(@ $order:ident)?, $addr:expr) => {
+        $crate::tag_atomic!($buf, D $(@ $order)?, $addr)
+    };
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr) => {
+        $crate::AtomicBuffer::lword::<$crate::byte_order!($($order)?)>($buf, $addr)
+    };
+    ($buf:expr, W $(@ $order:ident)?, $addr:expr, $bit:literal) => {{
+        $crate::bit_index_assert!(16, $bit);
+        let (byte, bit) =
+            <u16 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::AtomicBuffer::bit($buf, $addr + byte, bit)
+    }};
+    ($buf:expr, D $(@ $order:ident)?, $addr:expr, $bit:literal) => {{
+        $crate::bit_index_assert!(32, $bit);
+        let (byte, bit) =
+            <u32 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::AtomicBuffer::bit($buf, $addr + byte, bit)
+    }};
+    ($buf:expr, L $(@ $order:ident)?, $addr:expr, $bit:literal) => {{
+        $crate::bit_index_assert!(64, $bit);
+        let (byte, bit) =
+            <u64 as $crate::BitField>::bit_position::<$crate::byte_order!($($order)?)>($bit);
+        $crate::AtomicBuffer::bit($buf, $addr + byte, bit)
+    }};
+    ($buf:expr, LINT $(@ $order:ident)?, $addr:expr) => {
+        $crate::tag_atomic!($buf, L $(@ $order)?, $addr)
+    };
+    ($buf:expr, LREAL $(@ $order:ident)?, $addr:expr) => {
+        $crate::tag_atomic!($buf, L $(@ $order)?, $addr)
+    };
+    ($buf:expr, $addr:literal) => {
+        $crate::iec_tag!($crate::tag_atomic ($buf) $addr)
+    };
+    ($buf:expr, $addr1:expr, $addr2:expr) => {
+        $crate::AtomicBuffer::bit($buf, $addr1, $addr2)
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! tag_method {
-    ($vis:vis, $name:ident, mut, X, $addr1:literal, $addr2:literal) => {
+    (@attrs [$($attrs:tt)*] [#[alias] $($rest:tt)*] $($args:tt)*) => {
+        $crate::tag_method!(@attrs [$($attrs)*] [$($rest)*] $($args)*);
+    };
+    (@attrs [$($attrs:tt)*] [#[$($attr:tt)*] $($rest:tt)*] $($args:tt)*) => {
+        $crate::tag_method!(@attrs [$($attrs)* #[$($attr)*]] [$($rest)*] $($args)*);
+    };
+    (@attrs [$($attrs:tt)*] [] $($args:tt)*) => {
+        $crate::tag_method!([$($attrs)*] $($args)*);
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, $access:tt, $order:ty,
+        $kind:ident @ $tag_order:ident, $($addr:tt)+
+    ) => {
+        $crate::tag_method!(
+            [$($attrs)*] $vis, $name, $access, $crate::byte_order!($tag_order), $kind, $($addr)+
+        );
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&mut self) -> $crate::BitMut<'_> {
-            $crate::BitMut::new(&mut self.buf[$addr1], $addr2)
+        $vis fn $name(&mut self) -> $crate::BitFieldMut<'_, $crate::tag_type!($kind), $order> {
+            $crate::BitFieldMut::with_byte_order(&mut self.buf[$addr..], $low, $high)
         }
     };
-    ($vis:vis, $name:ident, mut, B, $addr:literal) => {
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&mut self) -> &mut u8 {
-            &mut self.buf[$addr]
+        $vis fn $name(&self) -> $crate::tag_type!($kind) {
+            $crate::BitField::bits(
+                <$crate::tag_type!($kind) as $crate::BitField>::read::<$order>(&self.buf[$addr..]),
+                $low,
+                $high,
+            )
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, set, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, set, $order, $kind, $addr);
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, cell, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<get_ $name>](&self) -> $crate::tag_type!($kind) {
+                let word: $crate::tag_type!($kind) =
+                    $crate::cell::CellValue::get::<$order>(&self.buf[$addr..]);
+                $crate::BitField::bits(word, $low, $high)
+            }
+
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<set_ $name>](&self, value: $crate::tag_type!($kind)) {
+                let word: $crate::tag_type!($kind) =
+                    $crate::cell::CellValue::get::<$order>(&self.buf[$addr..]);
+                let word = $crate::BitField::with_bits(word, $low, $high, value);
+                $crate::cell::CellValue::set::<$order>(&self.buf[$addr..], word);
+            }
         }
     };
-    ($vis:vis, $name:ident, mut, W, $addr:literal) => {
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, volatile, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&mut self) -> $crate::WordMut<'_> {
-            $crate::alignment_assert!(2, $addr);
-            $crate::WordMut::new((&mut self.buf[$addr..$addr + 2]).try_into().unwrap())
+        $vis fn $name(&self) -> $crate::tag_type!($kind) {
+            $crate::BitField::bits(
+                self.buf.read::<$crate::tag_type!($kind), $order>($addr),
+                $low,
+                $high,
+            )
         }
     };
-    ($vis:vis, $name:ident, mut, D, $addr:literal) => {
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, volatile_mut, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&mut self) -> $crate::DWordMut<'_> {
-            $crate::alignment_assert!(4, $addr);
-            $crate::DWordMut::new((&mut self.buf[$addr..$addr + 4]).try_into().unwrap())
+        $vis fn $name(
+            &mut self,
+        ) -> $crate::VolatileBitFieldMut<'_, $crate::tag_type!($kind), $order> {
+            self.buf.bit_field_mut($addr, $low, $high)
         }
     };
-    ($vis:vis, $name:ident, mut, L, $addr:literal) => {
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&mut self) -> $crate::LWordMut<'_> {
-            $crate::alignment_assert!(8, $addr);
-            $crate::LWordMut::new((&mut self.buf[$addr..$addr + 8]).try_into().unwrap())
+        $vis fn $name(&self) -> $crate::AtomicBitField<'_, $crate::tag_type!($kind), $order> {
+            self.buf.bit_field($addr, $low, $high)
         }
     };
-    ($vis:vis, $name:ident, mut, $addr1:literal, $addr2:literal) => {
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, $access:tt, $order:ty,
+        W, $addr:literal, $bit:literal
+    ) => {
+        $crate::tag_method!(@bit_in [$($attrs)*] $vis, $name, $access, $order, u16, $addr, $bit);
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, $access:tt, $order:ty,
+        D, $addr:literal, $bit:literal
+    ) => {
+        $crate::tag_method!(@bit_in [$($attrs)*] $vis, $name, $access, $order, u32, $addr, $bit);
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, $access:tt, $order:ty,
+        L, $addr:literal, $bit:literal
+    ) => {
+        $crate::tag_method!(@bit_in [$($attrs)*] $vis, $name, $access, $order, u64, $addr, $bit);
+    };
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
         $vis fn $name(&mut self) -> $crate::BitMut<'_> {
-            $crate::BitMut::new(&mut self.buf[$addr1], $addr2)
+            let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+            $crate::BitMut::new(&mut self.buf[$addr + byte], bit)
         }
     };
-    ($vis:vis, $name:ident, const, X, $addr1:literal, $addr2:literal) => {
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
         $vis fn $name(&self) -> bool {
-            self.buf[$addr1] & (1 << $addr2) != 0
+            let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+            self.buf[$addr + byte] & (1 << bit) != 0
+        }
+    };
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, set, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, set, $order, $addr, $bit);
+    };
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, cell, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<get_ $name>](&self) -> bool {
+                let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+                self.buf[$addr + byte].get() & (1 << bit) != 0
+            }
+
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<set_ $name>](&self, value: bool) {
+                let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+                let cell = &self.buf[$addr + byte];
+                cell.set((cell.get() & !(1 << bit)) | u8::from(value) << bit);
+            }
         }
     };
-    ($vis:vis, $name:ident, const, B, $addr:literal) => {
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, volatile, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&self) -> u8 {
-            self.buf[$addr]
+        $vis fn $name(&self) -> bool {
+            let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+            self.buf.bit($addr + byte, bit)
         }
     };
-    ($vis:vis, $name:ident, const, W, $addr:literal) => {
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, volatile_mut, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&self) -> u16 {
-            $crate::alignment_assert!(2, $addr);
-            u16::from_be_bytes(self.buf[$addr..$addr + 2].try_into().unwrap())
+        $vis fn $name(&mut self) -> $crate::VolatileBitMut<'_> {
+            let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+            self.buf.bit_mut($addr + byte, bit)
         }
     };
-    ($vis:vis, $name:ident, const, D, $addr:literal) => {
+    (
+        @bit_in [$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty,
+        $int:ident, $addr:literal, $bit:literal
+    ) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&self) -> u32 {
-            $crate::alignment_assert!(4, $addr);
-            u32::from_be_bytes(self.buf[$addr..$addr + 4].try_into().unwrap())
+        $vis fn $name(&self) -> $crate::AtomicBit<'_> {
+            let (byte, bit) = <$int as $crate::BitField>::bit_position::<$order>($bit);
+            self.buf.bit($addr + byte, bit)
         }
     };
-    ($vis:vis, $name:ident, const, L, $addr:literal) => {
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, X, $addr1:literal, $addr2:literal) => {
+        $($attrs)*
         #[inline(always)]
-        $vis fn $name(&self) -> u64 {
-            $crate::alignment_assert!(8, $addr);
-            u64::from_be_bytes(self.buf[$addr..$addr + 8].try_into().unwrap())
+        $vis fn $name(&mut self) -> $crate::BitMut<'_> {
+            $crate::BitMut::new(&mut self.buf[$addr1], $addr2)
         }
     };
-    ($vis:vis, $name:ident, const, $addr1:literal, $addr2:literal) => {
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, B, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> &mut u8 {
+            &mut self.buf[$addr]
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, W, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::WordMut<'_, $order> {
+            $crate::WordMut::with_byte_order((&mut self.buf[$addr..$addr + 2]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, D, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::DWordMut<'_, $order> {
+            $crate::DWordMut::with_byte_order((&mut self.buf[$addr..$addr + 4]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, L, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::LWordMut<'_, $order> {
+            $crate::LWordMut::with_byte_order((&mut self.buf[$addr..$addr + 8]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, SINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::SIntMut<'_> {
+            $crate::SIntMut::new(&mut self.buf[$addr])
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, INT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::IntMut<'_, $order> {
+            $crate::IntMut::with_byte_order((&mut self.buf[$addr..$addr + 2]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, DINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::DIntMut<'_, $order> {
+            $crate::DIntMut::with_byte_order((&mut self.buf[$addr..$addr + 4]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, LINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::LIntMut<'_, $order> {
+            $crate::LIntMut::with_byte_order((&mut self.buf[$addr..$addr + 8]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, REAL, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::RealMut<'_, $order> {
+            $crate::RealMut::with_byte_order((&mut self.buf[$addr..$addr + 4]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, LREAL, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::LRealMut<'_, $order> {
+            $crate::LRealMut::with_byte_order((&mut self.buf[$addr..$addr + 8]).try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, mut, $order:ty, $addr1:literal, $addr2:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::BitMut<'_> {
+            $crate::BitMut::new(&mut self.buf[$addr1], $addr2)
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, X, $addr1:literal, $addr2:literal
+    ) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> $crate::AtomicBit<'_> {
+            self.buf.bit($addr1, $addr2)
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, B, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> $crate::AtomicByte<'_> {
+            self.buf.byte($addr)
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, SINT, $addr:literal) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, atomic, $order, B, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, W, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicWord, word, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, INT, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicWord, word, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, D, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicDWord, dword, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, DINT, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicDWord, dword, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, REAL, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicDWord, dword, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, L, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicLWord, lword, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, LINT, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicLWord, lword, $order, $addr);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, LREAL, $addr:literal) => {
+        $crate::tag_method!(@atomic [$($attrs)*] $vis, $name, AtomicLWord, lword, $order, $addr);
+    };
+    (
+        @atomic [$($attrs:tt)*] $vis:vis, $name:ident, $Accessor:ident, $method:ident,
+        $order:ty, $addr:literal
+    ) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> $crate::$Accessor<'_, $order> {
+            self.buf.$method::<$order>($addr)
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, atomic, $order:ty, $addr1:literal, $addr2:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> $crate::AtomicBit<'_> {
+            self.buf.bit($addr1, $addr2)
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, set, $order:ty, X, $addr1:literal, $addr2:literal) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, set, $order, $addr1, $addr2);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, set, $order:ty, $kind:ident, $addr:literal) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<set_ $name>](&mut self, value: $crate::tag_type!($kind)) {
+                *self.$name() = value;
+            }
+
+            $($attrs)*
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<update_ $name>](
+                &mut self,
+                f: impl FnOnce($crate::tag_type!($kind)) -> $crate::tag_type!($kind),
+            ) {
+                let value = f(*self.$name());
+                *self.$name() = value;
+            }
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, set, $order:ty, $addr1:literal, $addr2:literal) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<set_ $name>](&mut self, value: bool) {
+                *self.$name() = value;
+            }
+
+            $($attrs)*
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<update_ $name>](&mut self, f: impl FnOnce(bool) -> bool) {
+                let value = f(*self.$name());
+                *self.$name() = value;
+            }
+
+            $($attrs)*
+            #[allow(dead_code)]
+            #[inline(always)]
+            $vis fn [<toggle_ $name>](&mut self) {
+                self.[<update_ $name>](|value| !value);
+            }
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, cell, $order:ty, X, $addr1:literal, $addr2:literal) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, cell, $order, $addr1, $addr2);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, cell, $order:ty, $kind:ident, $addr:literal) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<get_ $name>](&self) -> $crate::tag_type!($kind) {
+                $crate::cell::CellValue::get::<$order>(&self.buf[$addr..])
+            }
+
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<set_ $name>](&self, value: $crate::tag_type!($kind)) {
+                $crate::cell::CellValue::set::<$order>(&self.buf[$addr..], value);
+            }
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, cell, $order:ty, $addr1:literal, $addr2:literal) => {
+        $crate::paste_idents! {
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<get_ $name>](&self) -> bool {
+                self.buf[$addr1].get() & (1 << $addr2) != 0
+            }
+
+            $($attrs)*
+            #[inline(always)]
+            $vis fn [<set_ $name>](&self, value: bool) {
+                let byte = self.buf[$addr1].get() & !(1 << $addr2);
+                self.buf[$addr1].set(byte | u8::from(value) << $addr2);
+            }
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, volatile, $order:ty, X, $addr1:literal, $addr2:literal
+    ) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, volatile, $order, $addr1, $addr2);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, volatile, $order:ty, $kind:ident, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> $crate::tag_type!($kind) {
+            self.buf.read::<_, $order>($addr)
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, volatile, $order:ty, $addr1:literal, $addr2:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> bool {
+            self.buf.bit($addr1, $addr2)
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, volatile_mut, $order:ty,
+        X, $addr1:literal, $addr2:literal
+    ) => {
+        $crate::tag_method!([$($attrs)*] $vis, $name, volatile_mut, $order, $addr1, $addr2);
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, volatile_mut, $order:ty, $kind:ident, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::VolatileMut<'_, $crate::tag_type!($kind), $order> {
+            self.buf.get_mut($addr)
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, volatile_mut, $order:ty,
+        $addr1:literal, $addr2:literal
+    ) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&mut self) -> $crate::VolatileBitMut<'_> {
+            self.buf.bit_mut($addr1, $addr2)
+        }
+    };
+    (
+        [$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, X, $addr1:literal, $addr2:literal
+    ) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> bool {
+            self.buf[$addr1] & (1 << $addr2) != 0
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, B, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> u8 {
+            self.buf[$addr]
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, W, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> u16 {
+            <$order as $crate::ByteOrder>::read_u16(self.buf[$addr..$addr + 2].try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, D, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> u32 {
+            <$order as $crate::ByteOrder>::read_u32(self.buf[$addr..$addr + 4].try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, L, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> u64 {
+            <$order as $crate::ByteOrder>::read_u64(self.buf[$addr..$addr + 8].try_into().unwrap())
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, SINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> i8 {
+            self.buf[$addr] as i8
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, INT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> i16 {
+            <$order as $crate::ByteOrder>::read_u16(
+                self.buf[$addr..$addr + 2].try_into().unwrap(),
+            ) as i16
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, DINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> i32 {
+            <$order as $crate::ByteOrder>::read_u32(
+                self.buf[$addr..$addr + 4].try_into().unwrap(),
+            ) as i32
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, LINT, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> i64 {
+            <$order as $crate::ByteOrder>::read_u64(
+                self.buf[$addr..$addr + 8].try_into().unwrap(),
+            ) as i64
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, REAL, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> f32 {
+            f32::from_bits(<$order as $crate::ByteOrder>::read_u32(
+                self.buf[$addr..$addr + 4].try_into().unwrap(),
+            ))
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, LREAL, $addr:literal) => {
+        $($attrs)*
+        #[inline(always)]
+        $vis fn $name(&self) -> f64 {
+            f64::from_bits(<$order as $crate::ByteOrder>::read_u64(
+                self.buf[$addr..$addr + 8].try_into().unwrap(),
+            ))
+        }
+    };
+    ([$($attrs:tt)*] $vis:vis, $name:ident, const, $order:ty, $addr1:literal, $addr2:literal) => {
+        $($attrs)*
         #[inline(always)]
         $vis fn $name(&self) -> bool {
             self.buf[$addr1] & (1 << $addr2) != 0
@@ -314,6 +1478,16 @@ macro_rules! tag_method {
 ///   if you want).
 /// - The process image has a fixed size which is always enforced.
 /// - The tag addresses are in the format described in the [`tag!()`][`tag`] macro.
+/// - Attributes and doc comments of the tags are applied to the generated methods.
+/// - The generated structs implement the [`ProcessImage`] trait, the mutable one also
+///   [`ProcessImageMut`].
+/// - The tags of the process image are listed in the `TAGS` constant (see [`TagInfo`]).
+/// - Besides the accessor of each tag, the mutable struct has `set_<tag>(value)` and
+///   `update_<tag>(f)` methods, and `toggle_<tag>()` for bits.
+/// - The immutable struct can be compared to another snapshot of the process image with
+///   `diff()`, which yields the changed tags (see [`Diff`]).
+/// - With the `serde` crate feature, the generated structs implement `Serialize` as a map from
+///   tag name to value, e.g. `{"enable": true, "speed": 1500}`.
 /// - The process image buffer is referenced, for owned buffers,
 ///   see [`process_image_owned!{}`][`process_image_owned`].
 ///
@@ -343,62 +1517,685 @@ macro_rules! tag_method {
 /// let pi_slice = &pi_buf[..];
 /// let pi = PiExample::try_from(pi_slice).unwrap();
 ///
-/// // Mutable access:
-/// let pi_slice_mut = &mut pi_buf[..];
-/// let mut pi = PiExampleMut::try_from(pi_slice_mut).unwrap();
-/// *pi.temperature() = 1234;
-/// *pi.setpoint() = 72;
-/// *pi.sensor_left() = false;
+/// // Mutable access:
+/// let pi_slice_mut = &mut pi_buf[..];
+/// let mut pi = PiExampleMut::try_from(pi_slice_mut).unwrap();
+/// *pi.temperature() = 1234;
+/// *pi.setpoint() = 72;
+/// *pi.sensor_left() = false;
+///
+/// // Or with the setter methods:
+/// pi.set_temperature(1234);
+/// pi.update_setpoint(|setpoint| setpoint + 8);
+/// pi.toggle_sensor_right();
+/// ```
+///
+/// As mentioned above, you can also generate just the mutable or just the immutable version:
+///
+/// ```
+/// # let buffer_in = [0x00u8; 16];
+/// # let mut buffer_out = [0x00u8; 8];
+/// process_image::process_image! {
+///     pub struct PiInputs: 16 {
+///         pub sensor_left: (X, 0, 0),     // %IX0.0
+///         pub sensor_right: (X, 0, 1),    // %IX0.1
+///         pub temperature: (D, 12),       // %ID12
+///     }
+/// }
+/// process_image::process_image! {
+///     pub struct mut PiOutputs: 8 {
+///         pub indicator_green: (X, 1, 0), // %QX1.0
+///         pub indicator_red: (X, 1, 2),   // %QX1.2
+///         pub setpoint: (W, 2),           // %QW2
+///     }
+/// }
+///
+/// let inp = PiInputs::try_from(&buffer_in).unwrap();
+/// let mut out = PiOutputs::try_from(&mut buffer_out).unwrap();
+///
+/// let left_or_right = inp.sensor_left() || inp.sensor_right();
+/// *out.indicator_green() = !left_or_right;
+/// *out.indicator_red() = left_or_right;
+/// ```
+///
+/// ## Byte Order
+/// Multi-byte tags are accessed in big-endian order by default.  Append `@ little_endian` to the
+/// size of the process image to access all tags in little-endian order instead:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiEtherCat, mut PiEtherCatMut: 8 @ little_endian {
+///         pub status: (W, 0),
+///         pub position: (DINT, 4),
+///     }
+/// }
+///
+/// let buf = [0x37, 0x02, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff];
+/// let pi = PiEtherCat::from(&buf);
+/// assert_eq!(pi.status(), 0x0237);
+/// assert_eq!(pi.position(), -1);
+/// ```
+///
+/// ## Alignment
+/// Tags must be aligned to the size of their datatype by default.  For process images without
+/// alignment, the requirement can be relaxed by appending `align = N` after the size and byte
+/// order.  Tags then only need to be aligned to the smaller of their size and `N`:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiProfibus, mut PiProfibusMut: 7 @ big_endian, align = 1 {
+///         pub status: (B, 0),
+///         pub speed: (W, 1),
+///         pub position: (D, 3),
+///     }
+/// }
+///
+/// let mut buf = [0x00; 7];
+/// let mut pi = PiProfibusMut::from(&mut buf);
+/// *pi.speed() = 1500;
+/// *pi.position() = 0xdeadbeef;
+/// assert_eq!(buf, [0x00, 0x05, 0xdc, 0xde, 0xad, 0xbe, 0xef]);
+/// ```
+///
+/// ## Compile-Time Checks
+/// All tags are checked at compile time.  A tag that does not fit into the process image, an
+/// address that violates the alignment, or a bit index outside of its byte or word will fail the
+/// build:
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiTooSmall: 16 {
+///         pub speed: (W, 15),
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiMisaligned: 16, align = 8 {
+///         pub position: (D, 2),
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiBadBit: 16 {
+///         pub sensor: (X, 0, 8),
+///     }
+/// }
+/// ```
+///
+/// ## Overlapping Tags
+/// With the `no_overlap` option, the process image is additionally checked for tags that occupy
+/// the same bits.  Such tags fail the build, unless one of them is explicitly marked as an alias
+/// using the `#[alias]` attribute:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiDrive: 8, no_overlap {
+///         pub control: (B, 0),
+///         #[alias]
+///         pub enable: (X, 0, 0),
+///         #[alias]
+///         pub quick_stop: (X, 0, 2),
+///         pub speed: (W, 2),
+///         pub status: (D, 4),
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiDrive: 8, no_overlap {
+///         pub speed: (W, 2),
+///         pub status: (D, 0),
+///     }
+/// }
+/// ```
+///
+/// ## Process Areas
+/// The area of the process image can be given after its size with `in I` (inputs), `in Q`
+/// (outputs), or `in M` (internal memory).  Without an area, the process image is treated as
+/// memory.  The area is available as the `AREA` constant and is used for the addresses shown by
+/// the `Debug` implementation.
+///
+/// Inputs are only written by the I/O system, so no mutable accessors can be generated for a
+/// process image `in I`:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiInputs: 4 in I {
+///         pub door_closed: (X, 0, 3),
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let pi = PiInputs::from(&[0x08, 0x00, 0x05, 0xdc]);
+/// assert_eq!(PiInputs::AREA, process_image::Area::Input);
+/// assert_eq!(
+///     format!("{:?}", pi),
+///     "PiInputs { door_closed: %IX0.3 = true, speed: %IW2 = 1500 }",
+/// );
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiInputs, mut PiInputsMut: 4 in I {
+///         pub speed: (W, 2),
+///     }
+/// }
+/// ```
+///
+/// ## IEC Address Strings
+/// Tags can also be written as IEC 61131-3 address strings, exactly as they appear in wiring
+/// diagrams.  The strings are parsed at compile time.  If the process image has an area, all
+/// address strings must be in this area:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiStation: 16 in I {
+///         pub door_closed: "%IX0.3",
+///         pub mode: "%IB1",
+///         pub temperature: "%ID12",
+///         pub setpoint: (W, 2),
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiStation: 16 {
+///         pub temperature: "%ID12.32",
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiStation: 16 in I {
+///         pub lamp: "%QX0.0",
+///     }
+/// }
+/// ```
+///
+/// ## Per-Tag Byte Order
+/// The byte order can also be overridden for individual tags.  This is mostly useful for
+/// describing Modbus register maps where some values are transferred word-swapped:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiModbus: 12 {
+///         pub voltage: (REAL @ CDAB, 0),
+///         pub energy: (D @ CDAB, 4),
+///         pub serial: (D @ BADC, 8),
+///     }
+/// }
+///
+/// let buf = [0x00, 0x00, 0x43, 0x66, 0xe2, 0x40, 0x00, 0x01, 0x22, 0x11, 0x44, 0x33];
+/// let pi = PiModbus::from(&buf);
+/// assert_eq!(pi.voltage(), 230.0);
+/// assert_eq!(pi.energy(), 123456);
+/// assert_eq!(pi.serial(), 0x11223344);
+/// ```
+///
+/// ## Bit Fields
+/// Status and control words often pack small state fields into a few bits.  Such a field is
+/// declared by appending the range of its bits to a `B`, `W`, `D`, or `L` tag, e.g.
+/// `(W, 0, 4..=6)`.  The accessor returns the bits shifted down to bit 0, and the mutable accessor
+/// only writes the bits of the field, higher bits of the new value are ignored.  Bit fields only
+/// overlap the tags which occupy one of their bits in the process image:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiDrive, mut PiDriveMut: 4, no_overlap {
+///         pub state: (W, 0, 0..=3),
+///         pub warning: (W, 0, 7..=7),
+///         pub mode: (W, 0, 8..=10),
+///         pub speed: (INT, 2),
+///     }
+/// }
+///
+/// let mut buf = [0x02, 0x37, 0x00, 0x00];
+/// let pi = PiDrive::from(&buf);
+/// assert_eq!(pi.state(), 7);
+/// assert_eq!(pi.warning(), 0);
+/// assert_eq!(pi.mode(), 2);
+///
+/// let mut pi = PiDriveMut::from(&mut buf);
+/// *pi.mode() = 5;
+/// assert_eq!(buf, [0x05, 0x37, 0x00, 0x00]);
+/// # assert_eq!(<PiDrive as process_image::ProcessImage>::TAGS[2].bit_field(), Some(8..=10));
+/// ```
+///
+/// The bits are counted from the least significant bit of the word, so the byte which holds them
+/// depends on the byte order.  Bits `8..=11` of a big-endian word are in its first byte, which the
+/// bits `0..=3` of a little-endian word share:
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiDrive: 2, no_overlap {
+///         pub state: (W @ little_endian, 0, 0..=3),
+///         pub mode: (W, 0, 8..=11),
+///     }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiDrive: 4 {
+///         pub mode: (W, 0, 12..=16),
+///     }
+/// }
+/// ```
+///
+/// ## Bits of Words
+/// Bits can also be addressed inside a `W`, `D`, or `L` tag, like `%MW10.12`, so the bits of
+/// control and status words can be declared exactly as documented by the device.  The bit index
+/// counts from the least significant bit of the whole word, so the byte which holds the bit
+/// depends on the byte order.  Bit 12 of a big-endian word at address 10 is bit 4 of byte 10:
+///
+/// ```
+/// process_image::process_image! {
+///     pub struct PiDrive, mut PiDriveMut: 4, no_overlap {
+///         pub switch_on: (W, 0, 0),
+///         pub enable_operation: (W, 0, 3),
+///         pub halt: "%MW0.8",
+///         pub speed: (INT, 2),
+///     }
+/// }
+///
+/// let mut buf = [0x00; 4];
+/// let mut pi = PiDriveMut::from(&mut buf);
+/// *pi.switch_on() = true;
+/// *pi.halt() = true;
+/// assert_eq!(buf, [0x01, 0x01, 0x00, 0x00]);
+/// # assert!(PiDrive::from(&buf).halt());
+/// ```
+///
+/// With `no_overlap`, a bit of a word overlaps the bit tag of the same physical bit:
+///
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct PiDrive: 2, no_overlap {
+///         pub remote: (W, 0, 12),
+///         pub fault: (X, 0, 4),
+///     }
+/// }
+/// ```
+///
+/// ## Atomic Access
+/// With `struct atomic`, a process image on an [`AtomicBuffer`] is generated which can be shared
+/// between threads.  Its methods return the accessors of [`tag_atomic!()`][`crate::tag_atomic`],
+/// so setting a bit never loses a concurrent write to another bit of the same byte.  All tags of
+/// an atomic process image must be aligned to their size:
+///
+/// ```
+/// use core::sync::atomic::Ordering;
+/// use process_image::AtomicBuffer;
+///
+/// process_image::process_image! {
+///     pub struct atomic PiShared: 4 in Q {
+///         pub valve: (X, 0, 0),
+///         pub lamp: (X, 0, 1),
+///         pub setpoint: (W, 2),
+///     }
+/// }
+///
+/// let buf = AtomicBuffer::<4>::new();
+/// let pi = PiShared::from(&buf);
+/// std::thread::scope(|s| {
+///     s.spawn(|| pi.valve().store(true, Ordering::Relaxed));
+///     s.spawn(|| pi.lamp().store(true, Ordering::Relaxed));
+///     s.spawn(|| pi.setpoint().store(1500, Ordering::Relaxed));
+/// });
+/// assert_eq!(buf.load(), [0x03, 0x00, 0x05, 0xdc]);
 /// ```
 ///
-/// As mentioned above, you can also generate just the mutable or just the immutable version:
+/// ```compile_fail
+/// process_image::process_image! {
+///     pub struct atomic PiShared: 4 {
+///         pub setpoint: (W, 1),
+///     }
+/// }
+/// ```
+///
+/// ## Shared Mutable Access
+/// With `struct cell`, a view over `&[Cell<u8>]` is generated instead.  It has a
+/// `get_<tag>()` and a `set_<tag>()` method for each tag which both take `&self`, so any number
+/// of copies of the view can update the process image at the same time, e.g. from closures for
+/// different parts of a scan cycle.  Every `set_<tag>()` writes immediately, nothing is written
+/// back on drop.  The view can't be shared between threads.
 ///
 /// ```
-/// # let buffer_in = [0x00u8; 16];
-/// # let mut buffer_out = [0x00u8; 8];
 /// process_image::process_image! {
-///     pub struct PiInputs: 16 {
-///         pub sensor_left: (X, 0, 0),     // %IX0.0
-///         pub sensor_right: (X, 0, 1),    // %IX0.1
-///         pub temperature: (D, 12),       // %ID12
+///     pub struct cell PiOutputs: 4 in Q {
+///         pub valve: (X, 0, 0),
+///         pub lamp: (X, 0, 1),
+///         pub setpoint: (W, 2),
 ///     }
 /// }
+///
+/// let mut buf = [0x00; 4];
+/// let pi = PiOutputs::from(&mut buf);
+///
+/// let steps: [&dyn Fn(); 2] = [
+///     &|| pi.set_setpoint(pi.get_setpoint() + 1500),
+///     &|| pi.set_valve(pi.get_setpoint() > 1000),
+/// ];
+/// for step in steps {
+///     step();
+/// }
+/// pi.set_lamp(true);
+/// assert_eq!(pi.load(), [0x03, 0x00, 0x05, 0xdc]);
+/// ```
+///
+/// ## Volatile Access
+/// With `struct volatile`, the process image is accessed through a [`VolatileBuffer`], e.g. in
+/// the dual-ported RAM of a fieldbus ASIC.  All getters use volatile reads and the mutable
+/// accessors ([`VolatileBitMut`] and [`VolatileMut`]) write their value with volatile writes when
+/// they are dropped, so the compiler never merges or elides an access.  Like for the normal
+/// structs, you can generate both versions, `struct volatile mut` only, or `struct volatile`
+/// only:
+///
+/// ```
+/// use process_image::VolatileBuffer;
+///
 /// process_image::process_image! {
-///     pub struct mut PiOutputs: 8 {
-///         pub indicator_green: (X, 1, 0), // %QX1.0
-///         pub indicator_red: (X, 1, 2),   // %QX1.2
-///         pub setpoint: (W, 2),           // %QW2
+///     pub struct volatile PiDpram, mut PiDpramMut: 8 in Q {
+///         pub valve: (X, 0, 0),
+///         pub setpoint: (W, 2),
+///         pub position: "%QD4",
 ///     }
 /// }
 ///
-/// let inp = PiInputs::try_from(&buffer_in).unwrap();
-/// let mut out = PiOutputs::try_from(&mut buffer_out).unwrap();
+/// # let mut dpram = [0x00u8; 8];
+/// # let dpram = dpram.as_mut_ptr();
+/// // `dpram` points to the output area in the dual-ported RAM.
+/// let buf = unsafe { VolatileBuffer::<8>::from_ptr(dpram) };
+/// let mut pi = PiDpramMut::from(buf);
+/// *pi.valve() = true;
+/// *pi.setpoint() = 1500;
+/// *pi.position() += 10;
 ///
-/// let left_or_right = inp.sensor_left() || inp.sensor_right();
-/// *out.indicator_green() = !left_or_right;
-/// *out.indicator_red() = left_or_right;
+/// let pi = pi.as_ref();
+/// assert!(pi.valve());
+/// assert_eq!(pi.setpoint(), 1500);
+/// assert_eq!(pi.position(), 10);
 /// ```
 #[macro_export]
 macro_rules! process_image {
     (
         $( #[$meta:meta] )*
-        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal {
+        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @both
+            $( #[$meta] )*
+            $vis struct $ProcessImage, mut $ProcessImageMut: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct volatile $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @volatile_both
+            $( #[$meta] )*
+            $vis struct volatile $ProcessImage, mut $ProcessImageMut: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct volatile mut $ProcessImageMut:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @volatile_mut
+            $( #[$meta] )*
+            $vis struct volatile mut $ProcessImageMut: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct volatile $ProcessImage:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @volatile
+            $( #[$meta] )*
+            $vis struct volatile $ProcessImage: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct cell $ProcessImageCell:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @cell
+            $( #[$meta] )*
+            $vis struct cell $ProcessImageCell: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct atomic $ProcessImageAtomic:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @atomic
+            $( #[$meta] )*
+            $vis struct atomic $ProcessImageAtomic: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct mut $ProcessImageMut:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @mut
+            $( #[$meta] )*
+            $vis struct mut $ProcessImageMut: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        $( #[$meta:meta] )*
+        $vis:vis struct $ProcessImage:ident: $SIZE:literal
+            $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @const
+            $( #[$meta] )*
+            $vis struct $ProcessImage: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        @options [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        [align = $new_align:literal, $($opts:tt)*] $($rest:tt)*
+    ) => {
+        $crate::process_image! {
+            @options [$order, $new_align, $overlap, $area] [$($opts)*] $($rest)*
+        }
+    };
+    (
+        @options [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        [no_overlap, $($opts:tt)*] $($rest:tt)*
+    ) => {
+        $crate::process_image! {
+            @options [$order, $align, [no_overlap], $area] [$($opts)*] $($rest)*
+        }
+    };
+    (
+        @options [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        [$opt:ident $(= $val:literal)?, $($opts:tt)*] $($rest:tt)*
+    ) => {
+        ::core::compile_error!(::core::concat!(
+            "Unknown process image option `",
+            ::core::stringify!($opt),
+            "`",
+        ));
+    };
+    (@options [$order:ty, $align:expr, $overlap:tt, $area:tt] [] @owned $($rest:tt)*) => {
+        $crate::iec_tags! {
+            $crate::process_image_owned $area {
+                @both [$order, $align, $overlap, $area] $($rest)*
+            }
+        }
+    };
+    (@options [$order:ty, $align:expr, $overlap:tt, $area:tt] [] @$kind:ident $($rest:tt)*) => {
+        $crate::iec_tags! {
+            $crate::process_image $area {
+                @$kind [$order, $align, $overlap, $area] $($rest)*
+            }
+        }
+    };
+    (
+        @both [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal $fields:tt
+    ) => {
+        $crate::area_check!(mut, $area);
+        $crate::process_image! {
+            @views [$order, $align, $overlap, $area]
+            [$ProcessImage [$( #[$meta] )*] []]
+            [$ProcessImageMut [$( #[$meta] )*] []]
+            [mut, set]
+            $vis, $SIZE $fields
+        }
+    };
+    (
+        @mut [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct mut $ProcessImageMut:ident: $SIZE:literal $fields:tt
+    ) => {
+        $crate::area_check!(mut, $area);
+        // The immutable view only exists to be used as `ProcessImage::Ref`.
+        $crate::paste_idents! {
+            $crate::process_image! {
+                @views [$order, $align, $overlap, $area]
+                [[<$ProcessImageMut RefView>] [#[doc(hidden)]] [#[allow(dead_code)]]]
+                [$ProcessImageMut [$( #[$meta] )*] []]
+                [mut, set]
+                $vis, $SIZE $fields
+            }
+        }
+    };
+    (
+        @const [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct $ProcessImage:ident: $SIZE:literal $fields:tt
+    ) => {
+        // The mutable view only exists to be used as `ProcessImage::Mut`.  It does not provide
+        // mutable access to the tags or to the raw data.
+        $crate::paste_idents! {
+            $crate::process_image! {
+                @views [$order, $align, $overlap, $area]
+                [$ProcessImage [$( #[$meta] )*] []]
+                [[<$ProcessImage MutView>] [#[doc(hidden)]] [#[allow(dead_code)]]]
+                [const]
+                $vis, $SIZE $fields
+            }
+        }
+    };
+    (
+        @views [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        [$ProcessImage:ident [$( #[$meta:meta] )*] [$( #[$impl_meta:meta] )*]]
+        [$ProcessImageMut:ident [$( #[$meta_mut:meta] )*] [$( #[$impl_meta_mut:meta] )*]]
+        [$($access:ident),*]
+        $vis:vis, $SIZE:literal {
             $(
-                $( #[$field_meta:meta] )*
+                $( #[$($field_meta:tt)*] )*
                 $field_vis:vis $field_name:ident: ($($tag:tt)+)
             ),*
             $(,)?
         }
     ) => {
+        $crate::image_check!($align);
+        $(
+            $crate::tag_check!($field_name, $SIZE, $align, $($tag)+);
+        )*
+        $crate::overlap_check!($overlap, $order; $(
+            [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+        ),*);
+
         $( #[$meta] )*
         $vis struct $ProcessImage<'a> {
             buf: &'a [u8; $SIZE],
         }
 
+        $( #[$impl_meta] )*
         impl<'a> $ProcessImage<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $crate::tag_table!($order, $area; $(
+                [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+            ),*);
+
+            /// Iterate over the tags which changed from `self` to `other`.
+            #[allow(dead_code)]
+            pub fn diff<'b>(&'b self, other: &'b $ProcessImage<'_>) -> $crate::Diff<'b> {
+                $crate::Diff::new(Self::TAGS, &self.buf[..], &other.buf[..])
+            }
+
             $(
-                $( #[$field_meta] )*
-                $crate::tag_method!($vis, $field_name, const, $($tag)+);
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, const, $order, $($tag)+
+                );
             )*
         }
 
@@ -425,17 +2222,38 @@ macro_rules! process_image {
             }
         }
 
+        impl<'a> $crate::ProcessImage for $ProcessImage<'a> {
+            const SIZE: usize = $SIZE;
+            const TAGS: &'static [$crate::TagInfo] = Self::TAGS;
+            type Ref<'b> = $ProcessImage<'b>;
+            type Mut<'b> = $ProcessImageMut<'b>;
+
+            #[inline(always)]
+            fn as_bytes(&self) -> &[u8] {
+                &self.buf[..]
+            }
+        }
+
+        $crate::impl_serialize!($ProcessImage<'a>);
+
         impl<'a> ::core::fmt::Debug for $ProcessImage<'a> {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 f.debug_struct(::core::stringify!($ProcessImage))
                     $(
-                    .field(::core::stringify!($field_name), &self.$field_name())
+                    .field(
+                        ::core::stringify!($field_name),
+                        &$crate::DebugTag::new(
+                            $crate::tag_address!(Self::AREA, $($tag)+),
+                            $crate::tag_bit_field!($($tag)+),
+                            self.$field_name(),
+                        ),
+                    )
                     )*
                     .finish()
             }
         }
 
-        $( #[$meta] )*
+        $( #[$meta_mut] )*
         $vis struct $ProcessImageMut<'a> {
             buf: &'a mut [u8; $SIZE],
         }
@@ -463,135 +2281,411 @@ macro_rules! process_image {
             }
         }
 
-        impl<'a> ::core::convert::AsMut<[u8]> for $ProcessImageMut<'a> {
+        $crate::process_image!(@raw_mut [$($access),*] $ProcessImageMut);
+
+        impl<'a> $crate::ProcessImage for $ProcessImageMut<'a> {
+            const SIZE: usize = $SIZE;
+            const TAGS: &'static [$crate::TagInfo] = Self::TAGS;
+            type Ref<'b> = $ProcessImage<'b>;
+            type Mut<'b> = $ProcessImageMut<'b>;
+
             #[inline(always)]
-            fn as_mut(&mut self) -> &mut [u8] {
-                &mut self.buf[..]
+            fn as_bytes(&self) -> &[u8] {
+                &self.buf[..]
             }
         }
 
+        $crate::impl_serialize!($ProcessImageMut<'a>);
+
         impl<'a> ::core::fmt::Debug for $ProcessImageMut<'a> {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 let pi = $ProcessImage::from(&*self.buf);
                 f.debug_struct(::core::stringify!($ProcessImageMut))
                     $(
-                    .field(::core::stringify!($field_name), &pi.$field_name())
+                    .field(
+                        ::core::stringify!($field_name),
+                        &$crate::DebugTag::new(
+                            $crate::tag_address!(Self::AREA, $($tag)+),
+                            $crate::tag_bit_field!($($tag)+),
+                            pi.$field_name(),
+                        ),
+                    )
                     )*
                     .finish()
             }
         }
 
+        $( #[$impl_meta_mut] )*
         impl<'a> $ProcessImageMut<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $ProcessImage::TAGS;
+
+            $crate::process_image! {
+                @methods [$($access),*] $vis, $order {
+                    $(
+                        $( #[$($field_meta)*] )*
+                        $field_name: ($($tag)+),
+                    )*
+                }
+            }
+        }
+    };
+    // The hidden mutable view of a definition without `mut` gives no mutable access at all.
+    (@raw_mut [const] $ProcessImageMut:ident) => {};
+    (@raw_mut [$($access:ident),*] $ProcessImageMut:ident) => {
+        impl<'a> ::core::convert::AsMut<[u8]> for $ProcessImageMut<'a> {
+            #[inline(always)]
+            fn as_mut(&mut self) -> &mut [u8] {
+                &mut self.buf[..]
+            }
+        }
+
+        impl<'a> $crate::ProcessImageMut for $ProcessImageMut<'a> {
+            #[inline(always)]
+            fn as_bytes_mut(&mut self) -> &mut [u8] {
+                &mut self.buf[..]
+            }
+        }
+    };
+    (@methods [] $vis:vis, $order:ty { $($fields:tt)* }) => {};
+    (
+        @methods [$access:ident $(, $rest:ident)*] $vis:vis, $order:ty {
             $(
-                $( #[$field_meta] )*
-                $crate::tag_method!($vis, $field_name, mut, $($tag)+);
+                $( #[$($field_meta:tt)*] )*
+                $field_name:ident: ($($tag:tt)+),
             )*
         }
+    ) => {
+        $(
+            $crate::tag_method!(
+                @attrs [] [$( #[$($field_meta)*] )*]
+                $vis, $field_name, $access, $order, $($tag)+
+            );
+        )*
+        $crate::process_image! {
+            @methods [$($rest),*] $vis, $order {
+                $(
+                    $( #[$($field_meta)*] )*
+                    $field_name: ($($tag)+),
+                )*
+            }
+        }
     };
     (
+        @atomic [$order:ty, $align:expr, $overlap:tt, $area:tt]
         $( #[$meta:meta] )*
-        $vis:vis struct mut $ProcessImageMut:ident: $SIZE:literal {
+        $vis:vis struct atomic $ProcessImageAtomic:ident: $SIZE:literal {
             $(
-                $( #[$field_meta:meta] )*
+                $( #[$($field_meta:tt)*] )*
                 $field_vis:vis $field_name:ident: ($($tag:tt)+)
             ),*
             $(,)?
         }
     ) => {
+        $crate::image_check!($align);
+        $crate::area_check!(mut, $area);
+        // Atomic accesses must be naturally aligned, regardless of the `align` option.
+        $(
+            $crate::tag_check!($field_name, $SIZE, 8, $($tag)+);
+        )*
+        $crate::overlap_check!($overlap, $order; $(
+            [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+        ),*);
+
         $( #[$meta] )*
-        $vis struct $ProcessImageMut<'a> {
-            buf: &'a mut [u8; $SIZE],
+        #[derive(Clone, Copy)]
+        $vis struct $ProcessImageAtomic<'a> {
+            buf: &'a $crate::AtomicBuffer<$SIZE>,
         }
 
-        impl<'a> ::core::convert::From<&'a mut [u8; $SIZE]> for $ProcessImageMut<'a> {
+        impl<'a> $ProcessImageAtomic<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $crate::tag_table!($order, $area; $(
+                [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+            ),*);
+
+            $(
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, atomic, $order, $($tag)+
+                );
+            )*
+        }
+
+        impl<'a> ::core::convert::From<&'a $crate::AtomicBuffer<$SIZE>> for $ProcessImageAtomic<'a> {
             #[inline(always)]
-            fn from(buf: &'a mut [u8; $SIZE]) -> Self {
+            fn from(buf: &'a $crate::AtomicBuffer<$SIZE>) -> Self {
                 Self { buf }
             }
         }
 
-        impl<'a> ::core::convert::TryFrom<&'a mut [u8]> for $ProcessImageMut<'a> {
+        impl<'a> ::core::fmt::Debug for $ProcessImageAtomic<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let buf = self.buf.load();
+                let mut debug = f.debug_struct(::core::stringify!($ProcessImageAtomic));
+                for tag in Self::TAGS {
+                    debug.field(
+                        tag.name(),
+                        &$crate::DebugTag::of_tag(tag, ::core::format_args!("{}", tag.read(&buf))),
+                    );
+                }
+                debug.finish()
+            }
+        }
+    };
+    (
+        @cell [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct cell $ProcessImageCell:ident: $SIZE:literal {
+            $(
+                $( #[$($field_meta:tt)*] )*
+                $field_vis:vis $field_name:ident: ($($tag:tt)+)
+            ),*
+            $(,)?
+        }
+    ) => {
+        $crate::image_check!($align);
+        $crate::area_check!(mut, $area);
+        $(
+            $crate::tag_check!($field_name, $SIZE, $align, $($tag)+);
+        )*
+        $crate::overlap_check!($overlap, $order; $(
+            [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+        ),*);
+
+        $( #[$meta] )*
+        #[derive(Clone, Copy)]
+        $vis struct $ProcessImageCell<'a> {
+            buf: &'a [::core::cell::Cell<u8>; $SIZE],
+        }
+
+        impl<'a> $ProcessImageCell<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $crate::tag_table!($order, $area; $(
+                [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+            ),*);
+
+            /// Copy of the process image.
+            #[allow(dead_code)]
+            #[inline(always)]
+            pub fn load(&self) -> [u8; $SIZE] {
+                $crate::cell::load(self.buf)
+            }
+
+            $(
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, cell, $order, $($tag)+
+                );
+            )*
+        }
+
+        impl<'a> ::core::convert::From<&'a [::core::cell::Cell<u8>; $SIZE]> for $ProcessImageCell<'a> {
+            #[inline(always)]
+            fn from(buf: &'a [::core::cell::Cell<u8>; $SIZE]) -> Self {
+                Self { buf }
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<&'a [::core::cell::Cell<u8>]> for $ProcessImageCell<'a> {
             type Error = ::core::array::TryFromSliceError;
 
             #[inline(always)]
-            fn try_from(buf: &'a mut [u8]) -> Result<Self, Self::Error> {
+            fn try_from(buf: &'a [::core::cell::Cell<u8>]) -> Result<Self, Self::Error> {
                 buf.try_into().map(|buf| Self { buf })
             }
         }
-
-        impl<'a> ::core::convert::AsRef<[u8]> for $ProcessImageMut<'a> {
-            #[inline(always)]
-            fn as_ref(&self) -> &[u8] {
-                &self.buf[..]
+
+        impl<'a> ::core::convert::From<&'a mut [u8; $SIZE]> for $ProcessImageCell<'a> {
+            #[inline(always)]
+            fn from(buf: &'a mut [u8; $SIZE]) -> Self {
+                let buf = ::core::cell::Cell::from_mut(&mut buf[..]).as_slice_of_cells();
+                Self {
+                    buf: buf.try_into().unwrap(),
+                }
+            }
+        }
+
+        impl<'a> ::core::fmt::Debug for $ProcessImageCell<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let buf = self.load();
+                let mut debug = f.debug_struct(::core::stringify!($ProcessImageCell));
+                for tag in Self::TAGS {
+                    debug.field(
+                        tag.name(),
+                        &$crate::DebugTag::of_tag(tag, ::core::format_args!("{}", tag.read(&buf))),
+                    );
+                }
+                debug.finish()
+            }
+        }
+    };
+    (
+        @volatile_both [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct volatile $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @volatile [$order, $align, $overlap, $area]
+            $( #[$meta] )*
+            $vis struct volatile $ProcessImage: $SIZE {
+                $($fields)*
+            }
+        }
+        $crate::process_image! {
+            @volatile_mut [$order, $align, $overlap, $area]
+            $( #[$meta] )*
+            $vis struct volatile mut $ProcessImageMut: $SIZE {
+                $($fields)*
             }
         }
 
-        impl<'a> ::core::convert::AsMut<[u8]> for $ProcessImageMut<'a> {
+        impl<'a> $ProcessImageMut<'a> {
+            /// Immutable access to the process image.
+            #[allow(dead_code)]
             #[inline(always)]
-            fn as_mut(&mut self) -> &mut [u8] {
-                &mut self.buf[..]
+            pub fn as_ref(&self) -> $ProcessImage<'_> {
+                // SAFETY: The buffer is borrowed for the lifetime of the returned process image.
+                $ProcessImage::from(unsafe { $crate::VolatileBuffer::from_ptr(self.buf.as_ptr()) })
             }
         }
-
-        impl<'a> $ProcessImageMut<'a> {
-            $(
-                $( #[$field_meta] )*
-                $crate::tag_method!($vis, $field_name, mut, $($tag)+);
-            )*
-        }
     };
     (
+        @volatile [$order:ty, $align:expr, $overlap:tt, $area:tt]
         $( #[$meta:meta] )*
-        $vis:vis struct $ProcessImage:ident: $SIZE:literal {
+        $vis:vis struct volatile $ProcessImage:ident: $SIZE:literal {
             $(
-                $( #[$field_meta:meta] )*
+                $( #[$($field_meta:tt)*] )*
                 $field_vis:vis $field_name:ident: ($($tag:tt)+)
             ),*
             $(,)?
         }
     ) => {
+        $crate::image_check!($align);
+        $(
+            $crate::tag_check!($field_name, $SIZE, $align, $($tag)+);
+        )*
+        $crate::overlap_check!($overlap, $order; $(
+            [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+        ),*);
+
         $( #[$meta] )*
         $vis struct $ProcessImage<'a> {
-            buf: &'a [u8; $SIZE],
+            buf: $crate::VolatileBuffer<'a, $SIZE>,
         }
 
         impl<'a> $ProcessImage<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $crate::tag_table!($order, $area; $(
+                [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+            ),*);
+
             $(
-                $( #[$field_meta] )*
-                $crate::tag_method!($vis, $field_name, const, $($tag)+);
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, volatile, $order, $($tag)+
+                );
             )*
         }
 
-        impl<'a> ::core::convert::From<&'a [u8; $SIZE]> for $ProcessImage<'a> {
+        impl<'a> ::core::convert::From<$crate::VolatileBuffer<'a, $SIZE>> for $ProcessImage<'a> {
             #[inline(always)]
-            fn from(buf: &'a [u8; $SIZE]) -> Self {
+            fn from(buf: $crate::VolatileBuffer<'a, $SIZE>) -> Self {
                 Self { buf }
             }
         }
 
-        impl<'a> ::core::convert::TryFrom<&'a [u8]> for $ProcessImage<'a> {
-            type Error = ::core::array::TryFromSliceError;
-
-            #[inline(always)]
-            fn try_from(buf: &'a [u8]) -> Result<Self, Self::Error> {
-                buf.try_into().map(|buf| Self { buf })
+        impl<'a> ::core::fmt::Debug for $ProcessImage<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(::core::stringify!($ProcessImage))
+                    $(
+                    .field(
+                        ::core::stringify!($field_name),
+                        &$crate::DebugTag::new(
+                            $crate::tag_address!(Self::AREA, $($tag)+),
+                            $crate::tag_bit_field!($($tag)+),
+                            self.$field_name(),
+                        ),
+                    )
+                    )*
+                    .finish()
             }
         }
+    };
+    (
+        @volatile_mut [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct volatile mut $ProcessImageMut:ident: $SIZE:literal {
+            $(
+                $( #[$($field_meta:tt)*] )*
+                $field_vis:vis $field_name:ident: ($($tag:tt)+)
+            ),*
+            $(,)?
+        }
+    ) => {
+        $crate::image_check!($align);
+        $crate::area_check!(mut, $area);
+        $(
+            $crate::tag_check!($field_name, $SIZE, $align, $($tag)+);
+        )*
+        $crate::overlap_check!($overlap, $order; $(
+            [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+        ),*);
 
-        impl<'a> ::core::convert::AsRef<[u8]> for $ProcessImage<'a> {
+        $( #[$meta] )*
+        $vis struct $ProcessImageMut<'a> {
+            buf: $crate::VolatileBuffer<'a, $SIZE>,
+        }
+
+        impl<'a> $ProcessImageMut<'a> {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $crate::tag_table!($order, $area; $(
+                [$( #[$($field_meta)*] )*] $field_name: ($($tag)+)
+            ),*);
+
+            $(
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, volatile_mut, $order, $($tag)+
+                );
+            )*
+        }
+
+        impl<'a> ::core::convert::From<$crate::VolatileBuffer<'a, $SIZE>> for $ProcessImageMut<'a> {
             #[inline(always)]
-            fn as_ref(&self) -> &[u8] {
-                &self.buf[..]
+            fn from(buf: $crate::VolatileBuffer<'a, $SIZE>) -> Self {
+                Self { buf }
             }
         }
 
-        impl<'a> ::core::fmt::Debug for $ProcessImage<'a> {
+        impl<'a> ::core::fmt::Debug for $ProcessImageMut<'a> {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
-                f.debug_struct(::core::stringify!($ProcessImage))
-                    $(
-                    .field(::core::stringify!($field_name), &self.$field_name())
-                    )*
-                    .finish()
+                let buf = self.buf.load();
+                let mut debug = f.debug_struct(::core::stringify!($ProcessImageMut));
+                for tag in Self::TAGS {
+                    debug.field(
+                        tag.name(),
+                        &$crate::DebugTag::of_tag(tag, ::core::format_args!("{}", tag.read(&buf))),
+                    );
+                }
+                debug.finish()
             }
         }
     };
@@ -607,6 +2701,12 @@ macro_rules! process_image {
 /// - The tag addresses are in the format described in the [`tag!()`][`tag`] macro.
 /// - You can construct a `process_image_owned` from zeros (`new_zeroed()`) or from a
 ///   pre-initialized buffer by using `From<[u8; SIZE]` or `TryFrom<&[u8]>`.
+/// - The byte order and options can be selected like for [`process_image!{}`][`process_image`],
+///   e.g. `pub struct Pi, mut PiMut: 16 @ little_endian, align = 1, no_overlap { ... }`.
+/// - Both structs implement the [`ProcessImage`] and [`ProcessImageMut`] traits.
+/// - With the `serde` crate feature, both structs implement `Serialize` and the owned struct also
+///   implements `Deserialize`.  Tags missing from the input are zero and unknown tags are
+///   rejected.
 ///
 /// ## Example
 /// ```
@@ -639,13 +2739,58 @@ macro_rules! process_image {
 /// *pi.as_mut().setpoint() = 72;
 /// *pi.as_mut().sensor_left() = false;
 /// ```
+///
+/// ## Edge Detection
+/// With `edges` and a third name after the two struct names, an edge tracker for the process
+/// image is generated as well.  It keeps the process images of the current and the previous cycle
+/// and provides `<tag>_rising()` and `<tag>_falling()` methods for all bit tags, like the
+/// `R_TRIG` and `F_TRIG` function blocks of IEC 61131-3.  Call `latch()` once per cycle:
+///
+/// ```
+/// process_image::process_image_owned! {
+///     pub struct PiInputs, mut PiInputsMut, edges PiInputsEdges: 4 {
+///         pub btn_start: (X, 0, 0),
+///         pub btn_stop: (X, 0, 1),
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let mut edges = PiInputsEdges::new();
+///
+/// edges.latch(&PiInputs::from(&[0x01, 0x00, 0x00, 0x00]));
+/// assert!(edges.btn_start_rising());
+///
+/// edges.latch(&PiInputs::from(&[0x02, 0x00, 0x00, 0x00]));
+/// assert!(edges.btn_start_falling());
+/// assert!(edges.btn_stop_rising());
+/// assert_eq!(edges.current().btn_stop(), true);
+/// ```
 #[macro_export]
 macro_rules! process_image_owned {
     (
         $( #[$meta:meta] )*
-        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident: $SIZE:literal {
+        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident $(, edges $Edges:ident)?:
+            $SIZE:literal $(in $area:ident)? $(@ $order:ident)? $(, $opt:ident $(= $val:literal)?)* {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::process_image! {
+            @options [$crate::byte_order!($($order)?), $crate::DEFAULT_ALIGNMENT, [], [$($area)?]]
+            [$($opt $(= $val)?,)*]
+            @owned
+            $( #[$meta] )*
+            $vis struct $ProcessImage, mut $ProcessImageMut $(, edges $Edges)?: $SIZE {
+                $($fields)*
+            }
+        }
+    };
+    (
+        @both [$order:ty, $align:expr, $overlap:tt, $area:tt]
+        $( #[$meta:meta] )*
+        $vis:vis struct $ProcessImage:ident, mut $ProcessImageMut:ident $(, edges $Edges:ident)?:
+            $SIZE:literal {
             $(
-                $( #[$field_meta:meta] )*
+                $( #[$($field_meta:tt)*] )*
                 $field_vis:vis $field_name:ident: ($($tag:tt)+)
             ),*
             $(,)?
@@ -657,6 +2802,12 @@ macro_rules! process_image_owned {
         }
 
         impl $ProcessImage {
+            #[allow(dead_code)]
+            pub const AREA: $crate::Area = $crate::area!($area);
+
+            #[allow(dead_code)]
+            pub const TAGS: &'static [$crate::TagInfo] = $ProcessImageMut::TAGS;
+
             #[allow(dead_code)]
             #[inline(always)]
             pub fn new_zeroed() -> Self {
@@ -667,7 +2818,7 @@ macro_rules! process_image_owned {
 
             #[allow(dead_code)]
             #[inline(always)]
-            pub fn as_mut(&mut self) -> $ProcessImageMut {
+            pub fn as_mut(&mut self) -> $ProcessImageMut<'_> {
                 $ProcessImageMut::from(&mut self.buf)
             }
 
@@ -684,8 +2835,10 @@ macro_rules! process_image_owned {
             }
 
             $(
-                $( #[$field_meta] )*
-                $crate::tag_method!($vis, $field_name, const, $($tag)+);
+                $crate::tag_method!(
+                    @attrs [] [$( #[$($field_meta)*] )*]
+                    $vis, $field_name, const, $order, $($tag)+
+                );
             )*
         }
 
@@ -721,53 +2874,490 @@ macro_rules! process_image_owned {
             }
         }
 
+        $crate::paste_idents! {
+            impl $crate::ProcessImage for $ProcessImage {
+                const SIZE: usize = $SIZE;
+                const TAGS: &'static [$crate::TagInfo] = Self::TAGS;
+                type Ref<'b> = [<$ProcessImage RefView>]<'b>;
+                type Mut<'b> = $ProcessImageMut<'b>;
+
+                #[inline(always)]
+                fn as_bytes(&self) -> &[u8] {
+                    &self.buf[..]
+                }
+            }
+        }
+
+        $crate::impl_serialize!($ProcessImage);
+        $crate::impl_deserialize!($ProcessImage);
+
+        impl $crate::ProcessImageMut for $ProcessImage {
+            #[inline(always)]
+            fn as_bytes_mut(&mut self) -> &mut [u8] {
+                &mut self.buf[..]
+            }
+        }
+
         impl ::core::fmt::Debug for $ProcessImage {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 f.debug_struct(::core::stringify!($ProcessImage))
                     $(
-                    .field(::core::stringify!($field_name), &self.$field_name())
+                    .field(
+                        ::core::stringify!($field_name),
+                        &$crate::DebugTag::new(
+                            $crate::tag_address!(Self::AREA, $($tag)+),
+                            $crate::tag_bit_field!($($tag)+),
+                            self.$field_name(),
+                        ),
+                    )
                     )*
                     .finish()
             }
         }
 
-        $crate::process_image! {
-            $(#[$meta])*
-            $vis struct mut $ProcessImageMut: $SIZE {
-                $(
-                    $(#[$field_meta])*
-                    $field_vis $field_name: ($($tag)+),
-                )*
-            }
+        $crate::area_check!(mut, $area);
+        // The immutable view only exists to be used as `ProcessImage::Ref`.
+        $crate::paste_idents! {
+            $crate::process_image! {
+                @views [$order, $align, $overlap, $area]
+                [[<$ProcessImage RefView>] [#[doc(hidden)]] [#[allow(dead_code)]]]
+                [$ProcessImageMut [$(#[$meta])*] []]
+                [mut, set]
+                $vis, $SIZE {
+                    $(
+                        $(#[$($field_meta)*])*
+                        $field_vis $field_name: ($($tag)+),
+                    )*
+                }
+            }
+        }
+
+        $crate::process_image_owned! {
+            @edges $vis $ProcessImage, [$($Edges)?] $($field_name: ($($tag)+),)*
+        }
+    };
+    (@edges $vis:vis $ProcessImage:ident, [] $($rest:tt)*) => {};
+    (
+        @edges $vis:vis $ProcessImage:ident, [$Edges:ident]
+        $($field_name:ident: ($($tag:tt)+),)*
+    ) => {
+        /// Edge detection on the bit tags of
+        #[doc = ::core::concat!("[`", ::core::stringify!($ProcessImage), "`].")]
+        #[derive(Debug)]
+        $vis struct $Edges {
+            previous: $ProcessImage,
+            current: $ProcessImage,
+        }
+
+        impl $Edges {
+            #[allow(dead_code)]
+            #[inline(always)]
+            pub fn new() -> Self {
+                Self {
+                    previous: $ProcessImage::new_zeroed(),
+                    current: $ProcessImage::new_zeroed(),
+                }
+            }
+
+            /// Store the process image of the current cycle, the last one becomes the previous.
+            #[allow(dead_code)]
+            #[inline(always)]
+            pub fn latch(&mut self, pi: &$ProcessImage) {
+                self.previous.buf = self.current.buf;
+                self.current.buf = pi.buf;
+            }
+
+            /// Process image of the last call to `latch()`.
+            #[allow(dead_code)]
+            #[inline(always)]
+            pub fn current(&self) -> &$ProcessImage {
+                &self.current
+            }
+
+            /// Process image of the call to `latch()` before the last one.
+            #[allow(dead_code)]
+            #[inline(always)]
+            pub fn previous(&self) -> &$ProcessImage {
+                &self.previous
+            }
+
+            $(
+                $crate::edge_method!($vis, $field_name, $($tag)+);
+            )*
+        }
+
+        impl ::core::default::Default for $Edges {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+#[allow(
+    clippy::bool_assert_comparison,
+    clippy::unnecessary_fallible_conversions
+)]
+mod tests {
+    #[test]
+    fn tag_macro_smoke1() {
+        let mut pi = [0x55, 0xaa, 0x00, 0xff];
+
+        assert_eq!(tag!(&pi, X, 2, 0), false);
+        assert_eq!(tag!(&pi, 3, 0), true);
+        assert_eq!(tag!(&pi, X, 0, 0), true);
+        assert_eq!(tag!(&pi, 0, 1), false);
+
+        *tag_mut!(&mut pi, X, 0, 0) = false;
+        assert_eq!(tag!(&pi, X, 0, 0), false);
+
+        assert_eq!(tag!(&pi, B, 2), 0x00);
+        *tag_mut!(&mut pi, X, 2, 7) = true;
+        assert_eq!(tag!(&pi, B, 2), 0x80);
+
+        assert_eq!(tag!(&pi, W, 2), 0x80ff);
+        assert_eq!(*tag_mut!(&mut pi, W, 2), 0x80ff);
+        assert_eq!(tag!(&pi, D, 0), 0x54aa80ff);
+        assert_eq!(*tag_mut!(&mut pi, D, 0), 0x54aa80ff);
+
+        *tag_mut!(&mut pi, W, 2) = 0xbeef;
+        assert_eq!(tag!(&pi, W, 2), 0xbeef);
+    }
+
+    #[test]
+    fn tag_macro_signed() {
+        let mut pi = [0xff, 0x80, 0xff, 0xfe, 0x80, 0x00, 0x00, 0x00];
+
+        assert_eq!(tag!(&pi, SINT, 0), -1);
+        assert_eq!(tag!(&pi, SINT, 1), i8::MIN);
+        assert_eq!(tag!(&pi, INT, 2), -2);
+        assert_eq!(tag!(&pi, DINT, 4), i32::MIN);
+        assert_eq!(tag!(&pi, LINT, 0), -0x7f000180000000);
+
+        *tag_mut!(&mut pi, SINT, 0) = 127;
+        assert_eq!(tag!(&pi, B, 0), 0x7f);
+        *tag_mut!(&mut pi, INT, 2) = -1337;
+        assert_eq!(tag!(&pi, W, 2), 0xfac7);
+        *tag_mut!(&mut pi, DINT, 4) = -1;
+        assert_eq!(tag!(&pi, D, 4), 0xffffffff);
+        *tag_mut!(&mut pi, LINT, 0) = -2;
+        assert_eq!(tag!(&pi, L, 0), 0xfffffffffffffffe);
+        assert_eq!(*tag_mut!(&mut pi, LINT, 0), -2);
+    }
+
+    process_image! {
+        pub struct TestPiSigned, mut TestPiSignedMut: 8 {
+            pub offset: (SINT, 1),
+            pub temperature: (INT, 2),
+            pub position: (DINT, 4),
+        }
+    }
+
+    #[test]
+    fn pi_macro_signed() {
+        let mut pi_buffer = [0x00, 0xfb, 0xff, 0x38, 0xff, 0xff, 0xff, 0x00];
+
+        let pi = TestPiSigned::try_from(&pi_buffer).unwrap();
+        assert_eq!(pi.offset(), -5);
+        assert_eq!(pi.temperature(), -200);
+        assert_eq!(pi.position(), -256);
+
+        let mut pi = TestPiSignedMut::try_from(&mut pi_buffer).unwrap();
+        *pi.offset() -= 1;
+        *pi.temperature() = 215;
+        *pi.position() = -1;
+
+        let pi = TestPiSigned::try_from(&pi_buffer).unwrap();
+        assert_eq!(pi.offset(), -6);
+        assert_eq!(pi.temperature(), 215);
+        assert_eq!(pi.position(), -1);
+    }
+
+    #[test]
+    fn tag_macro_float() {
+        let mut pi = [0x40, 0x49, 0x0f, 0xdb, 0x00, 0x00, 0x00, 0x00];
+
+        assert_eq!(tag!(&pi, REAL, 0), core::f32::consts::PI);
+        assert_eq!(tag!(&pi, REAL, 4), 0.0);
+
+        *tag_mut!(&mut pi, REAL, 4) = -1.5;
+        assert_eq!(tag!(&pi, D, 4), 0xbfc00000);
+        assert_eq!(*tag_mut!(&mut pi, REAL, 4), -1.5);
+
+        *tag_mut!(&mut pi, LREAL, 0) = 1.0;
+        assert_eq!(tag!(&pi, L, 0), 0x3ff0000000000000);
+        assert_eq!(tag!(&pi, LREAL, 0), 1.0);
+    }
+
+    process_image_owned! {
+        pub struct TestPiFloat, mut TestPiFloatMut: 16 {
+            pub weight: (REAL, 4),
+            pub position: (LREAL, 8),
+        }
+    }
+
+    #[test]
+    fn pi_macro_float() {
+        let mut pi = TestPiFloat::new_zeroed();
+        *pi.as_mut().weight() = 12.5;
+        *pi.as_mut().position() = -0.25;
+
+        assert_eq!(pi.weight(), 12.5);
+        assert_eq!(pi.position(), -0.25);
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiFloat { weight: %MD4 = 12.5, position: %ML8 = -0.25 }"
+        );
+    }
+
+    #[test]
+    fn tag_macro_byte_order() {
+        let mut pi = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+        assert_eq!(tag!(&pi, W @ big_endian, 0), 0x0102);
+        assert_eq!(tag!(&pi, W @ little_endian, 0), 0x0201);
+        assert_eq!(tag!(&pi, D @ little_endian, 4), 0x08070605);
+        assert_eq!(tag!(&pi, L @ little_endian, 0), 0x0807060504030201);
+
+        *tag_mut!(&mut pi, INT @ little_endian, 2) = -2;
+        assert_eq!(tag!(&pi, W, 2), 0xfeff);
+        *tag_mut!(&mut pi, REAL @ little_endian, 4) = -1.5;
+        assert_eq!(tag!(&pi, D, 4), 0x0000c0bf);
+        assert_eq!(tag!(&pi, REAL @ little_endian, 4), -1.5);
+    }
+
+    process_image_owned! {
+        pub struct TestPiLittle, mut TestPiLittleMut: 8 @ little_endian {
+            pub status: (W, 0),
+            pub flag: (X, 1, 7),
+            pub temperature: (INT, 2),
+            pub counter: (D, 4),
+        }
+    }
+
+    #[test]
+    fn pi_macro_byte_order() {
+        let mut pi =
+            TestPiLittle::try_from(&[0x37, 0x82, 0x38, 0xff, 0x01, 0x00, 0x00, 0x00]).unwrap();
+        assert_eq!(pi.status(), 0x8237);
+        assert_eq!(pi.flag(), true);
+        assert_eq!(pi.temperature(), -200);
+        assert_eq!(pi.counter(), 1);
+
+        *pi.as_mut().counter() += 0x100;
+        *pi.as_mut().temperature() = 215;
+        *pi.as_mut().status() = 0x1234;
+        *pi.as_mut().flag() = true;
+        assert_eq!(
+            pi.as_slice(),
+            &[0x34, 0x92, 0xd7, 0x00, 0x01, 0x01, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn tag_macro_modbus_order() {
+        let mut pi = [0xcc, 0xdd, 0xaa, 0xbb, 0x00, 0x00, 0x00, 0x00];
+
+        assert_eq!(tag!(&pi, D @ ABCD, 0), 0xccddaabb);
+        assert_eq!(tag!(&pi, D @ CDAB, 0), 0xaabbccdd);
+        assert_eq!(tag!(&pi, D @ BADC, 0), 0xddccbbaa);
+        assert_eq!(tag!(&pi, D @ DCBA, 0), 0xbbaaddcc);
+        assert_eq!(tag!(&pi, W @ CDAB, 0), 0xccdd);
+        assert_eq!(tag!(&pi, W @ BADC, 0), 0xddcc);
+
+        *tag_mut!(&mut pi, L @ CDAB, 0) = 0x1122334455667788;
+        assert_eq!(pi, [0x77, 0x88, 0x55, 0x66, 0x33, 0x44, 0x11, 0x22]);
+        *tag_mut!(&mut pi, L @ BADC, 0) = 0x1122334455667788;
+        assert_eq!(pi, [0x22, 0x11, 0x44, 0x33, 0x66, 0x55, 0x88, 0x77]);
+        assert_eq!(tag!(&pi, L @ BADC, 0), 0x1122334455667788);
+    }
+
+    process_image! {
+        pub struct TestPiModbus, mut TestPiModbusMut: 8 @ little_endian {
+            pub status: (W, 0),
+            pub current: (W @ big_endian, 2),
+            pub energy: (DINT @ CDAB, 4),
+        }
+    }
+
+    #[test]
+    fn pi_macro_tag_byte_order() {
+        let mut pi_buffer = [0x01, 0x00, 0x00, 0x02, 0xff, 0xfe, 0xff, 0xff];
+
+        let pi = TestPiModbus::from(&pi_buffer);
+        assert_eq!(pi.status(), 1);
+        assert_eq!(pi.current(), 2);
+        assert_eq!(pi.energy(), -2);
+
+        let mut pi = TestPiModbusMut::from(&mut pi_buffer);
+        *pi.status() = 0x0102;
+        *pi.current() = 0x0304;
+        *pi.energy() = 0x05060708;
+        assert_eq!(pi_buffer, [0x02, 0x01, 0x03, 0x04, 0x07, 0x08, 0x05, 0x06]);
+    }
+
+    #[test]
+    fn tag_macro_align() {
+        let mut buf = [0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0x00];
+
+        assert_eq!(tag!(&buf, W, 1, align = 1), 0xadbe);
+        assert_eq!(tag!(&buf, D @ little_endian, 1, align = 1), 0xdeefbead);
+        assert_eq!(tag!(&buf, L, 1, align = 1), 0xadbeefdeadbeef00);
+        assert_eq!(tag!(&buf, D, 2, align = 2), 0xbeefdead);
+
+        *tag_mut!(&mut buf, INT, 7, align = 1) = -2;
+        assert_eq!(buf[7..], [0xff, 0xfe]);
+
+        let addr = 4;
+        assert_eq!(tag!(&buf, D, addr), 0xdeadbeff);
+    }
+
+    #[test]
+    #[should_panic(expected = "Double word address must be divisible by 2")]
+    fn tag_macro_align_partial() {
+        let buf = [0x00; 8];
+        let _ = tag!(&buf, D, 1, align = 2);
+    }
+
+    process_image_owned! {
+        pub struct TestPiPacked, mut TestPiPackedMut: 12 @ little_endian, align = 1 {
+            pub unaligned_word: (W, 1),
+            pub unaligned_dword: (D, 3),
+            pub unaligned_lword: (L, 4),
+        }
+    }
+
+    #[test]
+    fn pi_macro_align() {
+        let mut pi = TestPiPacked::try_from(&[
+            0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
+        ])
+        .unwrap();
+        assert_eq!(pi.unaligned_word(), 0xbead);
+        assert_eq!(pi.unaligned_dword(), 0xbeaddeef);
+        assert_eq!(pi.unaligned_lword(), 0xefbeaddeefbeadde);
+
+        *pi.as_mut().unaligned_word() = 0x1234;
+        *pi.as_mut().unaligned_dword() = 0x56789abc;
+        assert_eq!(
+            pi.as_slice()[..7],
+            [0xde, 0x34, 0x12, 0xbc, 0x9a, 0x78, 0x56]
+        );
+        *pi.as_mut().unaligned_lword() = 0;
+        assert_eq!(pi.unaligned_lword(), 0);
+    }
+
+    process_image_owned! {
+        pub struct TestPiAlias, mut TestPiAliasMut: 4, align = 2, no_overlap {
+            /// Control byte of the drive.
+            pub control: (B, 0),
+            /// Enable the drive.
+            #[alias]
+            pub enable: (X, 0, 0),
+            #[alias]
+            #[allow(dead_code)]
+            pub status: (B, 0),
+            pub flag: (X, 1, 0),
+            pub speed: (W, 2),
+        }
+    }
+
+    #[test]
+    fn pi_macro_alias() {
+        let mut pi = TestPiAlias::new_zeroed();
+        *pi.as_mut().enable() = true;
+        *pi.as_mut().flag() = true;
+        *pi.as_mut().speed() = 0x1234;
+        assert_eq!(pi.control(), 0x01);
+        assert_eq!(pi.enable(), true);
+        assert_eq!(pi.flag(), true);
+        assert_eq!(*pi.as_mut().control(), 0x01);
+        assert_eq!(pi.as_slice(), &[0x01, 0x01, 0x12, 0x34]);
+    }
+
+    #[test]
+    fn tag_macro_iec_address() {
+        let mut pi = [0x00, 0x04, 0xde, 0xad, 0xbe, 0xef, 0x00, 0x00];
+
+        assert_eq!(tag!(&pi, "%IX1.2"), true);
+        assert_eq!(tag!(&pi, "%I1.1"), false);
+        assert_eq!(tag!(&pi, "%QB1"), 0x04);
+        assert_eq!(tag!(&pi, "%MW2"), 0xdead);
+        assert_eq!(tag!(&pi, "%iw3", align = 1), 0xadbe);
+        assert_eq!(tag!(&pi, "%ID4"), 0xbeef0000);
+
+        *tag_mut!(&mut pi, "%QX0.7") = true;
+        *tag_mut!(&mut pi, "%QW6") = 0x1234;
+        *tag_mut!(&mut pi, "%QD1", align = 1) = 0xcafe0102;
+        assert_eq!(pi, [0x80, 0xca, 0xfe, 0x01, 0x02, 0xef, 0x12, 0x34]);
+    }
+
+    process_image! {
+        pub struct TestPiIec, mut TestPiIecMut: 8, no_overlap {
+            pub btn_start: "%IX0.0",
+            pub btn_stop: "%I0.1",
+            pub mode: "%IB1",
+            pub speed: (W, 2),
+            pub position: "%ID4",
         }
-    };
-}
+    }
 
-#[cfg(test)]
-mod tests {
     #[test]
-    fn tag_macro_smoke1() {
-        let mut pi = [0x55, 0xaa, 0x00, 0xff];
+    fn pi_macro_iec_address() {
+        let mut pi_buffer = [0x02, 0x07, 0x05, 0xdc, 0x00, 0x01, 0x00, 0x00];
 
-        assert_eq!(tag!(&pi, X, 2, 0), false);
-        assert_eq!(tag!(&pi, 3, 0), true);
-        assert_eq!(tag!(&pi, X, 0, 0), true);
-        assert_eq!(tag!(&pi, 0, 1), false);
+        let pi = TestPiIec::from(&pi_buffer);
+        assert_eq!(pi.btn_start(), false);
+        assert_eq!(pi.btn_stop(), true);
+        assert_eq!(pi.mode(), 7);
+        assert_eq!(pi.speed(), 1500);
+        assert_eq!(pi.position(), 0x00010000);
 
-        *tag_mut!(&mut pi, X, 0, 0) = false;
-        assert_eq!(tag!(&pi, X, 0, 0), false);
+        let mut pi = TestPiIecMut::from(&mut pi_buffer);
+        *pi.btn_start() = true;
+        *pi.btn_stop() = false;
+        *pi.mode() = 3;
+        *pi.speed() = 0;
+        *pi.position() = 42;
+        assert_eq!(pi_buffer, [0x01, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 42]);
+    }
 
-        assert_eq!(tag!(&pi, B, 2), 0x00);
-        *tag_mut!(&mut pi, X, 2, 7) = true;
-        assert_eq!(tag!(&pi, B, 2), 0x80);
+    process_image! {
+        pub struct TestPiInputs: 4 in I {
+            pub btn_start: (X, 0, 1),
+            pub mode: "%IB1",
+            pub speed: (INT @ little_endian, 2),
+        }
+    }
 
-        assert_eq!(tag!(&pi, W, 2), 0x80ff);
-        assert_eq!(*tag_mut!(&mut pi, W, 2), 0x80ff);
-        assert_eq!(tag!(&pi, D, 0), 0x54aa80ff);
-        assert_eq!(*tag_mut!(&mut pi, D, 0), 0x54aa80ff);
+    process_image_owned! {
+        pub struct TestPiOutputs, mut TestPiOutputsMut: 2 in Q @ little_endian {
+            pub lamp: "%QX0.0",
+            pub setpoint: (B, 1),
+        }
+    }
 
-        *tag_mut!(&mut pi, W, 2) = 0xbeef;
-        assert_eq!(tag!(&pi, W, 2), 0xbeef);
+    #[test]
+    fn pi_macro_area() {
+        assert_eq!(TestPiInputs::AREA, crate::Area::Input);
+        assert_eq!(TestPiOutputs::AREA, crate::Area::Output);
+        assert_eq!(TestPiOutputsMut::AREA, crate::Area::Output);
+        assert_eq!(TestPiAlias::AREA, crate::Area::Memory);
+
+        let pi = TestPiInputs::from(&[0x02, 0x03, 0xff, 0xff]);
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiInputs { btn_start: %IX0.1 = true, mode: %IB1 = 3, speed: %IW2 = -1 }"
+        );
+
+        let mut pi = TestPiOutputs::new_zeroed();
+        *pi.as_mut().lamp() = true;
+        *pi.as_mut().setpoint() = 42;
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiOutputs { lamp: %QX0.0 = true, setpoint: %QB1 = 42 }"
+        );
     }
 
     process_image! {
@@ -816,6 +3406,25 @@ mod tests {
         assert_eq!(tag!(&pi_buffer, B, 0), 1);
     }
 
+    #[test]
+    fn pi_setters() {
+        let mut pi_buffer = [0x00; 4];
+        let mut pi = TestPiMut::from(&mut pi_buffer);
+
+        pi.set_btn_start(true);
+        pi.set_speed(1000);
+        pi.update_speed(|speed| speed * 2 + 1);
+        pi.update_length(|length| length.wrapping_sub(1));
+        pi.toggle_btn_stop();
+        pi.toggle_btn_start();
+        pi.update_btn_reset(|reset| !reset);
+        assert_eq!(pi_buffer, [0xff, 0x06, 0x07, 0xd1]);
+
+        let mut pi = TestPiOwned::new_zeroed();
+        pi.as_mut().toggle_btn_stop();
+        assert!(pi.btn_stop());
+    }
+
     process_image_owned! {
         pub struct TestPiOwned, mut TestPiOwnedMut: 4 {
             pub btn_start: (X, 1, 0),
@@ -870,6 +3479,623 @@ mod tests {
         assert_eq!(tag!(&pi_buffer, B, 0), 1);
     }
 
+    #[test]
+    fn pi_macro_tags() {
+        use crate::{DataType, ProcessImage, Value};
+
+        let names: Vec<_> = TestPiAlias::TAGS.iter().map(|tag| tag.name()).collect();
+        assert_eq!(names, ["control", "enable", "status", "flag", "speed"]);
+        assert_eq!(TestPiAliasMut::TAGS.len(), 5);
+
+        let enable = &TestPiAlias::TAGS[1];
+        assert_eq!(enable.address().to_string(), "%MX0.0");
+        assert_eq!(enable.data_type(), DataType::Bool);
+        assert_eq!(enable.bit_index(), Some(0));
+        assert_eq!(
+            enable.doc_lines().collect::<Vec<_>>(),
+            ["Enable the drive."]
+        );
+        assert_eq!(TestPiAlias::TAGS[2].doc_lines().count(), 0);
+        assert_eq!(TestPiAlias::TAGS[4].bit_index(), None);
+
+        let tags = <TestPiModbus as ProcessImage>::TAGS;
+        assert_eq!(tags[2].address().to_string(), "%MD4");
+        assert_eq!(tags[2].data_type(), DataType::DInt);
+
+        let mut pi_buffer = [0x01, 0x00, 0x00, 0x02, 0xff, 0xfe, 0xff, 0xff];
+        let mut values = Vec::new();
+        TestPiModbusMut::from(&mut pi_buffer)
+            .visit_tags(|tag, value| values.push((tag.name(), value)));
+        assert_eq!(
+            values,
+            [
+                ("status", Value::Word(1)),
+                ("current", Value::Word(2)),
+                ("energy", Value::DInt(-2)),
+            ]
+        );
+
+        let mut values = Vec::new();
+        TestPiFloat::from(&[
+            0x00, 0x00, 0x00, 0x00, 0x41, 0x48, 0x00, 0x00, 0xbf, 0xd0, 0, 0, 0, 0, 0, 0,
+        ])
+        .visit_tags(|tag, value| values.push(format!("{} = {}", tag.address(), value)));
+        assert_eq!(values, ["%MD4 = 12.5", "%ML8 = -0.25"]);
+
+        let mut pi_buffer = [0x00; 4];
+        let tags = <TestPiOwned as ProcessImage>::TAGS;
+        tags[0].write(&mut pi_buffer, Value::Bool(true)).unwrap();
+        tags[3].write(&mut pi_buffer, Value::Word(1337)).unwrap();
+        assert_eq!(pi_buffer, [0x00, 0x01, 0x05, 0x39]);
+        assert_eq!(
+            tags[4].write(&mut pi_buffer, Value::Word(1)),
+            Err(crate::AccessError::TypeMismatch)
+        );
+    }
+
+    #[test]
+    fn pi_macro_diff() {
+        use crate::Value;
+
+        let previous = [0x01, 0x05, 0x05, 0x39];
+        let current = [0x01, 0x06, 0x05, 0x3a];
+        let previous = TestPi::from(&previous);
+        let current = TestPi::from(&current);
+
+        let changes: Vec<_> = previous
+            .diff(&current)
+            .map(|c| (c.tag().name(), c.old_value(), c.new_value()))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                ("btn_start", Value::Bool(true), Value::Bool(false)),
+                ("btn_stop", Value::Bool(false), Value::Bool(true)),
+                ("speed", Value::Word(1337), Value::Word(1338)),
+            ]
+        );
+
+        assert_eq!(previous.diff(&previous).count(), 0);
+        assert_eq!(current.diff(&previous).count(), 3);
+    }
+
+    process_image_owned! {
+        pub struct TestPiButtons, mut TestPiButtonsMut, edges TestPiButtonsEdges: 2 {
+            pub btn_start: (X, 0, 0),
+            pub btn_stop: (0, 1),
+            pub btn_reset: "%MX1.7",
+            pub counter: (B, 1),
+        }
+    }
+
+    #[test]
+    fn pi_owned_edges() {
+        let mut edges = TestPiButtonsEdges::default();
+        assert_eq!(edges.btn_start_rising(), false);
+        assert_eq!(edges.btn_start_falling(), false);
+
+        edges.latch(&TestPiButtons::from(&[0x01, 0x80]));
+        assert_eq!(edges.btn_start_rising(), true);
+        assert_eq!(edges.btn_stop_rising(), false);
+        assert_eq!(edges.btn_reset_rising(), true);
+
+        edges.latch(&TestPiButtons::from(&[0x03, 0x80]));
+        assert_eq!(edges.btn_start_rising(), false);
+        assert_eq!(edges.btn_start_falling(), false);
+        assert_eq!(edges.btn_stop_rising(), true);
+        assert_eq!(edges.btn_reset_rising(), false);
+
+        let mut pi = TestPiButtons::new_zeroed();
+        *pi.as_mut().btn_start() = false;
+        *pi.as_mut().btn_stop() = true;
+        *pi.as_mut().btn_reset() = false;
+        *pi.as_mut().counter() &= 0x7f;
+        edges.latch(&pi);
+        assert_eq!(edges.btn_start_falling(), true);
+        assert_eq!(edges.btn_stop_falling(), false);
+        assert_eq!(edges.btn_reset_falling(), true);
+        assert_eq!(edges.previous().counter(), 0x80);
+        assert_eq!(edges.current().counter(), 0x00);
+    }
+
+    process_image_owned! {
+        pub struct TestPiWordBitButtons, mut TestPiWordBitButtonsMut,
+            edges TestPiWordBitButtonsEdges: 8 @ little_endian
+        {
+            pub halt: (W, 0, 8),
+            pub ready: "%MD4.31",
+        }
+    }
+
+    #[test]
+    fn pi_owned_edges_word_bits() {
+        let mut pi = TestPiWordBitButtons::new_zeroed();
+        let mut edges = TestPiWordBitButtonsEdges::new();
+        *pi.as_mut().halt() = true;
+        *pi.as_mut().ready() = true;
+        edges.latch(&pi);
+        assert!(edges.halt_rising());
+        assert!(edges.ready_rising());
+
+        *pi.as_mut().halt() = false;
+        edges.latch(&pi);
+        assert!(edges.halt_falling());
+        assert!(!edges.ready_falling());
+    }
+
+    process_image! {
+        pub struct mut TestPiMutOnly: 2 in Q {
+            pub valve: (X, 0, 0),
+        }
+    }
+
+    fn fill<P: crate::ProcessImageMut>(pi: &mut P, value: u8) -> usize {
+        pi.as_bytes_mut().fill(value);
+        P::SIZE
+    }
+
+    fn view<P: crate::ProcessImage>(pi: &P) -> P::Ref<'_> {
+        P::Ref::try_from(pi.as_bytes()).ok().unwrap()
+    }
+
+    #[test]
+    fn pi_traits() {
+        let mut pi = TestPiOwned::new_zeroed();
+        assert_eq!(fill(&mut pi, 0xff), 4);
+        assert_eq!(pi.speed(), 0xffff);
+        assert_eq!(view(&pi).speed(), 0xffff);
+        assert_eq!(fill(&mut pi.as_mut(), 0x01), 4);
+        assert_eq!(crate::ProcessImage::as_bytes(&pi), &[0x01; 4]);
+
+        let mut buf = [0x00; 4];
+        assert_eq!(fill(&mut TestPiMut::from(&mut buf), 0x80), 4);
+        assert_eq!(view(&TestPiMut::from(&mut buf)).btn_start(), false);
+        assert_eq!(view(&TestPi::from(&buf)).length(), 0x80);
+
+        let mut buf = [0x00; 2];
+        assert_eq!(fill(&mut TestPiMutOnly::from(&mut buf), 0x01), 2);
+        assert_eq!(*TestPiMutOnly::from(&mut buf).valve(), true);
+        assert_eq!(view(&TestPiMutOnly::from(&mut buf)).valve(), true);
+
+        let mut buf = [0x01; 4];
+        let pi = <TestPiInputs as crate::ProcessImage>::Mut::try_from(&mut buf[..]).unwrap();
+        assert_eq!(crate::ProcessImage::as_bytes(&pi), &[0x01; 4]);
+        assert_eq!(pi.speed(), 0x0101);
+
+        let mut buf = [0x00; 4];
+        let mut pi = <TestPiOwned as crate::ProcessImage>::Mut::try_from(&mut buf[..]).unwrap();
+        *pi.speed() = 1337;
+        assert_eq!(buf, [0x00, 0x00, 0x05, 0x39]);
+    }
+
+    process_image! {
+        pub struct atomic TestPiAtomic: 8 in Q {
+            pub valve: (X, 0, 0),
+            pub lamp: "%QX0.1",
+            #[alias]
+            pub flags: (B, 0),
+            pub setpoint: (INT @ little_endian, 2),
+            pub position: (D, 4),
+        }
+    }
+
+    #[test]
+    fn pi_atomic() {
+        use core::sync::atomic::Ordering;
+
+        let buf = crate::AtomicBuffer::<8>::new();
+        let pi = TestPiAtomic::from(&buf);
+        std::thread::scope(|s| {
+            for _ in 0..4 {
+                s.spawn(|| {
+                    for _ in 0..1_000 {
+                        pi.valve().toggle(Ordering::Relaxed);
+                        pi.lamp().store(true, Ordering::Relaxed);
+                    }
+                });
+            }
+        });
+        assert_eq!(pi.flags().load(Ordering::Relaxed), 0x02);
+
+        pi.setpoint().store(-2i16 as u16, Ordering::Relaxed);
+        pi.position().store(0xdeadbeef, Ordering::Relaxed);
+        assert_eq!(buf.load(), [0x02, 0x00, 0xfe, 0xff, 0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiAtomic { valve: %QX0.0 = false, lamp: %QX0.1 = true, flags: %QB0 = 2, \
+             setpoint: %QW2 = -2, position: %QD4 = 3735928559 }",
+        );
+    }
+
+    process_image! {
+        pub struct atomic TestPiWordBitsAtomic: 2 @ little_endian {
+            pub halt: (W, 0, 8),
+        }
+    }
+
+    #[test]
+    fn pi_atomic_word_bits() {
+        use core::sync::atomic::Ordering;
+
+        let buf = crate::AtomicBuffer::<2>::new();
+        TestPiWordBitsAtomic::from(&buf)
+            .halt()
+            .store(true, Ordering::Relaxed);
+        assert_eq!(buf.load(), [0x00, 0x01]);
+        assert!(tag_atomic!(&buf, W @ little_endian, 0, 8).load(Ordering::Relaxed));
+    }
+
+    process_image! {
+        pub struct atomic TestPiBitFieldsAtomic: 4 @ little_endian {
+            pub state: (W, 0, 0..=3),
+            pub mode: (W, 0, 8..=10),
+            pub flags: (B, 2, 4..=7),
+        }
+    }
+
+    #[test]
+    fn pi_atomic_bit_fields() {
+        use core::sync::atomic::Ordering;
+
+        let buf = crate::AtomicBuffer::<4>::new();
+        let pi = TestPiBitFieldsAtomic::from(&buf);
+        std::thread::scope(|s| {
+            s.spawn(|| pi.state().store(0x1f, Ordering::Relaxed));
+            s.spawn(|| pi.mode().store(0x5, Ordering::Relaxed));
+            s.spawn(|| pi.flags().store(0xa, Ordering::Relaxed));
+        });
+        assert_eq!(pi.state().load(Ordering::Relaxed), 0xf);
+        assert_eq!(pi.mode().load(Ordering::Relaxed), 0x5);
+        assert_eq!(pi.flags().load(Ordering::Relaxed), 0xa);
+        assert_eq!(buf.load(), [0x0f, 0x05, 0xa0, 0x00]);
+    }
+
+    process_image! {
+        pub struct cell TestPiCell: 8 in Q {
+            pub valve: (X, 0, 0),
+            pub lamp: "%QX0.1",
+            #[alias]
+            pub flags: (B, 0),
+            pub setpoint: (INT @ little_endian, 2),
+            pub position: (REAL, 4),
+        }
+    }
+
+    #[test]
+    fn pi_cell() {
+        let mut buf = [0x00; 8];
+        let pi = TestPiCell::from(&mut buf);
+        let copy = pi;
+        let set_lamp = |on| copy.set_lamp(on);
+
+        pi.set_valve(true);
+        set_lamp(true);
+        pi.set_valve(false);
+        assert_eq!(pi.get_flags(), 0x02);
+        pi.set_flags(pi.get_flags() | 0x80);
+        assert!(copy.get_lamp());
+        assert!(!copy.get_valve());
+
+        pi.set_setpoint(-2);
+        copy.set_position(1.5);
+        assert_eq!(pi.get_setpoint(), -2);
+        assert_eq!(pi.get_position(), 1.5);
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiCell { valve: %QX0.0 = false, lamp: %QX0.1 = true, flags: %QB0 = 130, \
+             setpoint: %QW2 = -2, position: %QD4 = 1.5 }",
+        );
+        assert_eq!(buf, [0x82, 0x00, 0xfe, 0xff, 0x3f, 0xc0, 0x00, 0x00]);
+
+        let cells = [const { core::cell::Cell::new(0xff) }; 8];
+        let pi = TestPiCell::try_from(&cells[..]).unwrap();
+        assert_eq!(pi.get_setpoint(), -1);
+        assert!(TestPiCell::try_from(&cells[1..]).is_err());
+    }
+
+    process_image! {
+        pub struct cell TestPiBitFieldsCell: 2 @ little_endian {
+            pub state: (W, 0, 0..=3),
+            pub mode: (W, 0, 8..=10),
+        }
+    }
+
+    #[test]
+    fn pi_cell_bit_fields() {
+        let cells = [const { core::cell::Cell::new(0x00) }; 2];
+        let pi = TestPiBitFieldsCell::try_from(&cells[..]).unwrap();
+        pi.set_mode(3);
+        pi.set_state(0x1f);
+        assert_eq!(pi.get_mode(), 3);
+        assert_eq!(pi.get_state(), 0xf);
+        assert_eq!(pi.load(), [0x0f, 0x03]);
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiBitFieldsCell { state: %MW0.0..=3 = 15, mode: %MW0.8..=10 = 3 }",
+        );
+    }
+
+    process_image! {
+        pub struct cell TestPiWordBitsCell: 4 {
+            pub halt: (W, 0, 8),
+            pub fault: (D, 0, 20),
+        }
+    }
+
+    #[test]
+    fn pi_cell_word_bits() {
+        let cells = [const { core::cell::Cell::new(0x00) }; 4];
+        let pi = TestPiWordBitsCell::try_from(&cells[..]).unwrap();
+        pi.set_halt(true);
+        pi.set_fault(true);
+        assert!(pi.get_halt() && pi.get_fault());
+        assert_eq!(pi.load(), [0x01, 0x10, 0x00, 0x00]);
+    }
+
+    process_image! {
+        pub struct volatile TestPiVolatile, mut TestPiVolatileMut: 8 in Q @ little_endian {
+            pub valve: (X, 0, 0),
+            pub lamp: "%QX0.1",
+            #[alias]
+            pub flags: (B, 0),
+            pub setpoint: (INT @ big_endian, 2),
+            pub position: (REAL, 4),
+        }
+    }
+
+    process_image! {
+        pub struct volatile TestPiVolatileInputs: 4 in I, align = 1 {
+            pub status: (SINT, 0),
+            pub speed: (W, 1),
+        }
+    }
+
+    #[test]
+    fn pi_volatile() {
+        let mut mem = [0x00; 8];
+        let mut pi = TestPiVolatileMut::from(crate::VolatileBuffer::from(&mut mem));
+        *pi.lamp() = true;
+        *pi.setpoint() = -2;
+        *pi.position() = 1.5;
+        assert_eq!(pi.as_ref().valve(), false);
+        *pi.valve() = true;
+        assert_eq!(pi.as_ref().flags(), 0x03);
+        assert_eq!(
+            format!("{:?}", pi.as_ref()),
+            "TestPiVolatile { valve: %QX0.0 = true, lamp: %QX0.1 = true, flags: %QB0 = 3, \
+             setpoint: %QW2 = -2, position: %QD4 = 1.5 }",
+        );
+        assert_eq!(
+            format!("{:?}", pi),
+            format!("{:?}", pi.as_ref()).replace("Volatile", "VolatileMut")
+        );
+        *pi.flags() |= 0x80;
+        assert_eq!(mem, [0x83, 0x00, 0xff, 0xfe, 0x00, 0x00, 0xc0, 0x3f]);
+
+        let mut mem = [0x80, 0x05, 0xdc, 0x00];
+        let pi = TestPiVolatileInputs::from(crate::VolatileBuffer::from(&mut mem));
+        assert_eq!(pi.status(), -128);
+        assert_eq!(pi.speed(), 1500);
+    }
+
+    process_image! {
+        pub struct volatile TestPiBitFieldsVolatile, mut TestPiBitFieldsVolatileMut: 2 {
+            pub mode: (W, 0, 8..=10),
+            pub state: (W, 0, 0..=3),
+        }
+    }
+
+    #[test]
+    fn pi_volatile_bit_fields() {
+        let mut mem = [0x03, 0x00];
+        let pi = TestPiBitFieldsVolatile::from(crate::VolatileBuffer::from(&mut mem));
+        assert_eq!(pi.mode(), 3);
+
+        let mut mem = [0xa3, 0xff];
+        let mut pi = TestPiBitFieldsVolatileMut::from(crate::VolatileBuffer::from(&mut mem));
+        *pi.mode() = 0x5;
+        *pi.state() = 0x12;
+        assert_eq!(pi.as_ref().mode(), 0x5);
+        assert_eq!(pi.as_ref().state(), 0x2);
+        assert_eq!(mem, [0xa5, 0xf2]);
+    }
+
+    process_image! {
+        pub struct volatile TestPiWordBitsVolatile, mut TestPiWordBitsVolatileMut: 2 {
+            pub halt: (W, 0, 8),
+        }
+    }
+
+    #[test]
+    fn pi_volatile_word_bits() {
+        let mut mem = [0x00; 2];
+        let mut pi = TestPiWordBitsVolatileMut::from(crate::VolatileBuffer::from(&mut mem));
+        *pi.halt() = true;
+        assert!(pi.as_ref().halt());
+        assert_eq!(mem, [0x01, 0x00]);
+    }
+
+    #[test]
+    fn tag_macro_bit_fields() {
+        let mut buf = [0x00; 4];
+        *tag_mut!(&mut buf, W @ little_endian, 2, 12..=15) = 0xff;
+        *tag_mut!(&mut buf, B, 0, 1..=2) = 0x2;
+        assert_eq!(buf, [0x04, 0x00, 0x00, 0xf0]);
+        assert_eq!(tag!(&buf, W @ little_endian, 2, 12..=15), 0xf);
+        assert_eq!(tag!(&buf, D, 0, 4..=7), 0xf);
+        assert_eq!(tag!(&buf, B, 0, 0..=7), 0x04);
+    }
+
+    #[test]
+    #[should_panic(expected = "Bit field must be an ascending range within the width of the tag")]
+    fn bit_field_descending() {
+        let _ = crate::BitField::bits(0xffu8, 5, 2);
+    }
+
+    process_image! {
+        pub struct TestPiBitFields, mut TestPiBitFieldsMut: 8 @ little_endian, no_overlap {
+            pub state: (W, 0, 0..=3),
+            pub mode: (W, 0, 8..=10),
+            pub flags: (B, 2, 4..=7),
+            pub counter: (D @ big_endian, 4, 20..=31),
+        }
+    }
+
+    #[test]
+    fn pi_macro_bit_fields() {
+        let mut buf = [0x37, 0x02, 0xa5, 0x00, 0xab, 0xc0, 0x00, 0x00];
+        let pi = TestPiBitFields::from(&buf);
+        assert_eq!(pi.state(), 0x7);
+        assert_eq!(pi.mode(), 0x2);
+        assert_eq!(pi.flags(), 0xa);
+        assert_eq!(pi.counter(), 0xabc);
+
+        let mut pi = TestPiBitFieldsMut::from(&mut buf);
+        *pi.mode() = 0xd;
+        pi.set_state(0);
+        pi.update_flags(|flags| flags + 1);
+        *pi.counter() = 0x123;
+        assert_eq!(buf, [0x30, 0x05, 0xb5, 0x00, 0x12, 0x30, 0x00, 0x00]);
+        assert_eq!(
+            format!("{:?}", TestPiBitFields::from(&buf)),
+            "TestPiBitFields { state: %MW0.0..=3 = 0, mode: %MW0.8..=10 = 5, \
+             flags: %MB2.4..=7 = 11, counter: %MD4.20..=31 = 291 }",
+        );
+    }
+
+    #[test]
+    fn pi_macro_bit_field_tags() {
+        use crate::{AccessError, Value};
+
+        let mut buf = [0x30, 0x05, 0xb5, 0x00, 0x12, 0x30, 0x00, 0x00];
+        let tag = &TestPiBitFields::TAGS[1];
+        assert_eq!(tag.bit_field(), Some(8..=10));
+        assert_eq!(TestPiBitFields::TAGS[3].bit_field(), Some(20..=31));
+        assert_eq!(tag.read(&buf), Value::Word(5));
+        assert_eq!(
+            tag.write(&mut buf, Value::Byte(1)),
+            Err(AccessError::TypeMismatch)
+        );
+        tag.write(&mut buf, Value::Word(2)).unwrap();
+        assert_eq!(buf[..2], [0x30, 0x02]);
+    }
+
+    process_image! {
+        pub struct TestPiBitFieldsBytes: 2, no_overlap {
+            pub mode: (W, 0, 8..=11),
+            pub flags: (B, 1),
+        }
+    }
+
+    #[test]
+    fn pi_macro_bit_fields_no_overlap() {
+        let pi = TestPiBitFieldsBytes::from(&[0x05, 0xff]);
+        assert_eq!(pi.mode(), 0x5);
+        assert_eq!(pi.flags(), 0xff);
+    }
+
+    #[test]
+    fn tag_macro_word_bits() {
+        let mut buf = [0x00; 8];
+        *tag_mut!(&mut buf, W, 0, 12) = true;
+        *tag_mut!(&mut buf, D @ little_endian, 4, 9) = true;
+        *tag_mut!(&mut buf, "%MW2.0") = true;
+        assert_eq!(buf, [0x10, 0x00, 0x00, 0x01, 0x00, 0x02, 0x00, 0x00]);
+        assert!(tag!(&buf, W, 0, 12));
+        assert!(!tag!(&buf, W @ little_endian, 0, 12));
+        assert!(tag!(&buf, "%MD4.17"));
+        assert!(tag!(&buf, L, 0, 60));
+    }
+
+    process_image_owned! {
+        pub struct TestPiWordBits, mut TestPiWordBitsMut: 8 @ little_endian, no_overlap {
+            pub switch_on: (W, 0, 0),
+            pub halt: (W, 0, 8),
+            pub fault: (W @ big_endian, 2, 3),
+            pub ready: "%MD4.31",
+        }
+    }
+
+    #[test]
+    fn pi_macro_word_bits() {
+        let mut pi = TestPiWordBits::new_zeroed();
+        let mut pim = pi.as_mut();
+        *pim.switch_on() = true;
+        pim.set_halt(true);
+        pim.toggle_fault();
+        *pim.ready() = true;
+        assert_eq!(
+            pi.as_slice(),
+            [0x01, 0x01, 0x00, 0x08, 0x00, 0x00, 0x00, 0x80]
+        );
+        assert_eq!(
+            format!("{:?}", pi),
+            "TestPiWordBits { switch_on: %MW0.0 = true, halt: %MW0.8 = true, \
+             fault: %MW2.3 = true, ready: %MD4.31 = true }",
+        );
+    }
+
+    #[test]
+    fn pi_macro_word_bit_tags() {
+        use crate::{DataType, Value};
+
+        let tag = &TestPiWordBits::TAGS[1];
+        assert_eq!(tag.address().to_string(), "%MW0.8");
+        assert_eq!(tag.data_type(), DataType::Bool);
+        assert_eq!(tag.bit_index(), Some(8));
+        let mut buf = [0x00; 8];
+        tag.write(&mut buf, Value::Bool(true)).unwrap();
+        assert_eq!(buf, [0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert_eq!(tag.read(&buf), Value::Bool(true));
+    }
+
+    process_image! {
+        pub struct TestPiWordBitsBytes: 2, no_overlap {
+            pub remote: (W, 0, 12),
+            pub fault: (X, 1, 4),
+        }
+    }
+
+    #[test]
+    fn pi_macro_word_bits_no_overlap() {
+        let pi = TestPiWordBitsBytes::from(&[0x10, 0x00]);
+        assert!(pi.remote());
+        assert!(!pi.fault());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn pi_serde() {
+        let mut pi = TestPiOwned::from(&[0x07, 0x05, 0x05, 0x39]);
+        let json = serde_json::to_string(&pi).unwrap();
+        assert_eq!(
+            json,
+            r#"{"btn_start":true,"btn_stop":false,"btn_reset":true,"speed":1337,"length":7}"#
+        );
+        assert_eq!(serde_json::to_string(&pi.as_mut()).unwrap(), json);
+
+        let pi: TestPiOwned = serde_json::from_str(&json).unwrap();
+        assert_eq!(pi.as_slice(), &[0x07, 0x05, 0x05, 0x39]);
+
+        // Tags missing from the input are zero.
+        let pi: TestPiOwned = serde_json::from_str(r#"{"btn_stop":true}"#).unwrap();
+        assert_eq!(pi.as_slice(), &[0x00, 0x02, 0x00, 0x00]);
+
+        let err = serde_json::from_str::<TestPiOwned>(r#"{"torque":1}"#).unwrap_err();
+        assert!(err.to_string().starts_with("unknown tag `torque`"));
+        assert!(serde_json::from_str::<TestPiOwned>(r#"{"length":256}"#).is_err());
+
+        let pi = TestPiSigned::from(&[0x00, 0xfb, 0xff, 0x38, 0xff, 0xff, 0xff, 0x00]);
+        assert_eq!(
+            serde_json::to_string(&pi).unwrap(),
+            r#"{"offset":-5,"temperature":-200,"position":-256}"#
+        );
+
+        let pi: TestPiFloat = serde_json::from_str(r#"{"weight":12.5,"position":-0.25}"#).unwrap();
+        assert_eq!(pi.weight(), 12.5);
+        assert_eq!(pi.position(), -0.25);
+    }
+
     #[test]
     #[cfg_attr(
         not(feature = "allow_unaligned_tags"),
@@ -895,8 +4121,10 @@ mod tests {
         assert_eq!(tag!(&buf, W, 1), 0xcafe);
     }
 
+    // Unaligned tags in a process image are rejected at compile time unless alignment-enforcement
+    // is relaxed with the `align` option.  The `allow_unaligned_tags` feature has no effect here.
     process_image_owned! {
-        pub struct TestPiPanic, mut TestPiPanicMut: 12 {
+        pub struct TestPiUnaligned, mut TestPiUnalignedMut: 12, align = 1 {
             pub unaligned_word: (W, 1),
             pub unaligned_dword: (D, 2),
             pub unaligned_lword: (L, 4),
@@ -904,12 +4132,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Word address must be divisible by 2")
-    )]
     fn test_unaligned_word() {
-        let pi = TestPiPanic::try_from(&[
+        let pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -917,12 +4141,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Word address must be divisible by 2")
-    )]
     fn test_unaligned_word_mut() {
-        let mut pi = TestPiPanic::try_from(&[
+        let mut pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -931,12 +4151,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Double word address must be divisible by 4")
-    )]
     fn test_unaligned_dword() {
-        let pi = TestPiPanic::try_from(&[
+        let pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -944,12 +4160,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Double word address must be divisible by 4")
-    )]
     fn test_unaligned_dword_mut() {
-        let mut pi = TestPiPanic::try_from(&[
+        let mut pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -958,12 +4170,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Long word address must be divisible by 8")
-    )]
     fn test_unaligned_lword() {
-        let pi = TestPiPanic::try_from(&[
+        let pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -971,12 +4179,8 @@ mod tests {
     }
 
     #[test]
-    #[cfg_attr(
-        not(feature = "allow_unaligned_tags"),
-        should_panic(expected = "Long word address must be divisible by 8")
-    )]
     fn test_unaligned_lword_mut() {
-        let mut pi = TestPiPanic::try_from(&[
+        let mut pi = TestPiUnaligned::try_from(&[
             0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef, 0xde, 0xad, 0xbe, 0xef,
         ])
         .unwrap();
@@ -0,0 +1,189 @@
+//! Serde support for process images, enabled by the `serde` crate feature.
+//!
+//! Process images are serialized as a map from tag name to the typed value of the tag, e.g.
+//! `{"enable": true, "speed": 1500}` in JSON.
+
+#[cfg(feature = "serde")]
+mod imp {
+    use core::fmt;
+
+    use serde::de::{self, DeserializeSeed, MapAccess, Visitor};
+    use serde::ser::SerializeMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::image::{ProcessImage, ProcessImageMut};
+    use crate::tag_info::{DataType, TagInfo};
+    use crate::value::Value;
+
+    impl Serialize for Value {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match *self {
+                Value::Bool(v) => serializer.serialize_bool(v),
+                Value::Byte(v) => serializer.serialize_u8(v),
+                Value::Word(v) => serializer.serialize_u16(v),
+                Value::DWord(v) => serializer.serialize_u32(v),
+                Value::LWord(v) => serializer.serialize_u64(v),
+                Value::SInt(v) => serializer.serialize_i8(v),
+                Value::Int(v) => serializer.serialize_i16(v),
+                Value::DInt(v) => serializer.serialize_i32(v),
+                Value::LInt(v) => serializer.serialize_i64(v),
+                Value::Real(v) => serializer.serialize_f32(v),
+                Value::LReal(v) => serializer.serialize_f64(v),
+            }
+        }
+    }
+
+    /// Serialize a process image as a map of all its tags.
+    #[doc(hidden)]
+    pub fn serialize_image<P, S>(pi: &P, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: ProcessImage + ?Sized,
+        S: Serializer,
+    {
+        let buf = pi.as_bytes();
+        let mut map = serializer.serialize_map(Some(P::TAGS.len()))?;
+        for tag in P::TAGS {
+            map.serialize_entry(tag.name(), &tag.read(buf))?;
+        }
+        map.end()
+    }
+
+    /// Deserialize a map of tags into a (zeroed) process image.
+    ///
+    /// Tags which are missing from the map keep their value in `pi`.
+    #[doc(hidden)]
+    pub fn deserialize_image<'de, P, D>(mut pi: P, deserializer: D) -> Result<P, D::Error>
+    where
+        P: ProcessImageMut,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(ImageVisitor {
+            tags: P::TAGS,
+            buf: pi.as_bytes_mut(),
+        })?;
+        Ok(pi)
+    }
+
+    struct ImageVisitor<'b> {
+        tags: &'static [TagInfo],
+        buf: &'b mut [u8],
+    }
+
+    impl<'de> Visitor<'de> for ImageVisitor<'_> {
+        type Value = ();
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of process image tags")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+            while let Some(tag) = map.next_key_seed(TagSeed(self.tags))? {
+                let value = map.next_value_seed(ValueSeed(tag.data_type()))?;
+                tag.write(self.buf, value).map_err(de::Error::custom)?;
+            }
+            Ok(())
+        }
+    }
+
+    /// Look up a tag by its name.
+    struct TagSeed(&'static [TagInfo]);
+
+    impl<'de> DeserializeSeed<'de> for TagSeed {
+        type Value = &'static TagInfo;
+
+        fn deserialize<D: Deserializer<'de>>(
+            self,
+            deserializer: D,
+        ) -> Result<Self::Value, D::Error> {
+            deserializer.deserialize_identifier(self)
+        }
+    }
+
+    impl Visitor<'_> for TagSeed {
+        type Value = &'static TagInfo;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a tag name")
+        }
+
+        fn visit_str<E: de::Error>(self, name: &str) -> Result<Self::Value, E> {
+            self.0
+                .iter()
+                .find(|tag| tag.name() == name)
+                .ok_or_else(|| E::custom(format_args!("unknown tag `{}`", name)))
+        }
+    }
+
+    /// Deserialize a value of the given data type.
+    struct ValueSeed(DataType);
+
+    impl<'de> DeserializeSeed<'de> for ValueSeed {
+        type Value = Value;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+            Ok(match self.0 {
+                DataType::Bool => Value::Bool(bool::deserialize(deserializer)?),
+                DataType::Byte => Value::Byte(u8::deserialize(deserializer)?),
+                DataType::Word => Value::Word(u16::deserialize(deserializer)?),
+                DataType::DWord => Value::DWord(u32::deserialize(deserializer)?),
+                DataType::LWord => Value::LWord(u64::deserialize(deserializer)?),
+                DataType::SInt => Value::SInt(i8::deserialize(deserializer)?),
+                DataType::Int => Value::Int(i16::deserialize(deserializer)?),
+                DataType::DInt => Value::DInt(i32::deserialize(deserializer)?),
+                DataType::LInt => Value::LInt(i64::deserialize(deserializer)?),
+                DataType::Real => Value::Real(f32::deserialize(deserializer)?),
+                DataType::LReal => Value::LReal(f64::deserialize(deserializer)?),
+            })
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+pub use imp::{deserialize_image, serialize_image};
+
+/// Implement `Serialize` for a generated process image struct.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serialize {
+    ($ProcessImage:ident $(<$lt:lifetime>)?) => {
+        impl$(<$lt>)? $crate::serde::Serialize for $ProcessImage$(<$lt>)? {
+            fn serialize<S: $crate::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> ::core::result::Result<S::Ok, S::Error> {
+                $crate::serialize::serialize_image(self, serializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_serialize {
+    ($($t:tt)*) => {};
+}
+
+/// Implement `Deserialize` for a generated owned process image struct.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_deserialize {
+    ($ProcessImage:ident) => {
+        impl<'de> $crate::serde::Deserialize<'de> for $ProcessImage {
+            fn deserialize<D: $crate::serde::Deserializer<'de>>(
+                deserializer: D,
+            ) -> ::core::result::Result<Self, D::Error> {
+                $crate::serialize::deserialize_image(Self::new_zeroed(), deserializer)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_deserialize {
+    ($($t:tt)*) => {};
+}
@@ -0,0 +1,236 @@
+use core::sync::atomic::{fence, AtomicU8, AtomicUsize, Ordering};
+
+/// Process image of `N` bytes shared between one writer and many readers, using a sequence lock.
+///
+/// Readers always get a consistent copy of the process image, no tag can ever be torn by a
+/// concurrent write.  The writer never waits for readers, instead a reader retries when the image
+/// was written while it was copying it.  This works in `no_std` with `core::sync::atomic`, the
+/// image can be put into a `static`.
+///
+/// Concurrent writes are serialized, but the image is meant to be written by a single task.
+///
+/// # Example
+/// ```
+/// use process_image::SharedImage;
+///
+/// process_image::process_image! {
+///     pub struct PiOutputs, mut PiOutputsMut: 4 in Q {
+///         pub valve: (X, 0, 0),
+///         pub setpoint: (W, 2),
+///     }
+/// }
+///
+/// static OUTPUTS: SharedImage<4> = SharedImage::new();
+///
+/// // Control task:
+/// OUTPUTS.write(|buf| {
+///     let mut pi = PiOutputsMut::from(buf);
+///     *pi.valve() = true;
+///     *pi.setpoint() = 1500;
+/// });
+///
+/// // Diagnostics in any other thread:
+/// let setpoint = OUTPUTS.read(|buf| PiOutputs::from(buf).setpoint());
+/// assert_eq!(setpoint, 1500);
+/// ```
+#[derive(Debug)]
+pub struct SharedImage<const N: usize> {
+    seq: AtomicUsize,
+    buf: [AtomicU8; N],
+}
+
+impl<const N: usize> SharedImage<N> {
+    /// Create a zeroed shared process image.
+    pub const fn new() -> Self {
+        Self {
+            seq: AtomicUsize::new(0),
+            buf: [const { AtomicU8::new(0) }; N],
+        }
+    }
+
+    /// Copy of the current process image.
+    pub fn load(&self) -> [u8; N] {
+        let mut copy = [0x00; N];
+        loop {
+            let seq = self.seq.load(Ordering::Acquire);
+            if seq % 2 != 0 {
+                // A write is in progress.
+                core::hint::spin_loop();
+                continue;
+            }
+
+            for (byte, shared) in copy.iter_mut().zip(&self.buf) {
+                *byte = shared.load(Ordering::Relaxed);
+            }
+
+            fence(Ordering::Acquire);
+            if self.seq.load(Ordering::Relaxed) == seq {
+                return copy;
+            }
+        }
+    }
+
+    /// Call `f` with a consistent copy of the current process image.
+    pub fn read<R>(&self, f: impl FnOnce(&[u8; N]) -> R) -> R {
+        f(&self.load())
+    }
+
+    /// Replace the process image.
+    pub fn store(&self, buf: &[u8; N]) {
+        let mut lock = self.lock();
+        for (shared, byte) in self.buf.iter().zip(buf) {
+            shared.store(*byte, Ordering::Relaxed);
+        }
+        lock.written();
+    }
+
+    /// Modify the process image with `f`.
+    ///
+    /// Readers see either the old or the new process image, never anything in between.  When `f`
+    /// panics, the process image is left unchanged.
+    pub fn write<R>(&self, f: impl FnOnce(&mut [u8; N]) -> R) -> R {
+        let mut lock = self.lock();
+        let mut copy = [0x00; N];
+        for (byte, shared) in copy.iter_mut().zip(&self.buf) {
+            *byte = shared.load(Ordering::Relaxed);
+        }
+
+        let result = f(&mut copy);
+
+        for (shared, byte) in self.buf.iter().zip(&copy) {
+            shared.store(*byte, Ordering::Relaxed);
+        }
+        lock.written();
+        result
+    }
+
+    /// Mark a write in progress by making the sequence odd.
+    fn lock(&self) -> WriteLock<'_> {
+        loop {
+            let seq = self.seq.load(Ordering::Relaxed);
+            if seq % 2 == 0
+                && self
+                    .seq
+                    .compare_exchange_weak(
+                        seq,
+                        seq.wrapping_add(1),
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    )
+                    .is_ok()
+            {
+                fence(Ordering::Release);
+                return WriteLock {
+                    seq: &self.seq,
+                    unlocked: seq,
+                };
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Write in progress on a [`SharedImage`], ended when dropped.
+///
+/// Unless the write completed, the previous sequence is restored, so a panic while the image is
+/// locked does not block all readers and writers forever.  The shared bytes are only written after
+/// the closure of [`SharedImage::write()`] returned, so they are still unchanged then.
+struct WriteLock<'a> {
+    seq: &'a AtomicUsize,
+    unlocked: usize,
+}
+
+impl WriteLock<'_> {
+    /// Mark the process image as changed.
+    fn written(&mut self) {
+        self.unlocked = self.unlocked.wrapping_add(2);
+    }
+}
+
+impl Drop for WriteLock<'_> {
+    fn drop(&mut self) {
+        self.seq.store(self.unlocked, Ordering::Release);
+    }
+}
+
+impl<const N: usize> Default for SharedImage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::process_image! {
+        pub struct TestPiCounter, mut TestPiCounterMut: 8 {
+            pub low: (D, 0),
+            pub high: (D, 4),
+        }
+    }
+
+    #[test]
+    fn read_write() {
+        let shared = SharedImage::<8>::default();
+        assert_eq!(shared.load(), [0x00; 8]);
+
+        shared.store(&[0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02]);
+        let high = shared.write(|buf| {
+            *TestPiCounterMut::from(&mut *buf).low() += 1;
+            TestPiCounter::from(&*buf).high()
+        });
+        assert_eq!(high, 2);
+        assert_eq!(shared.read(|buf| TestPiCounter::from(buf).low()), 2);
+    }
+
+    #[test]
+    fn panic_in_write() {
+        let shared = SharedImage::<8>::new();
+        shared.store(&[0x01; 8]);
+
+        let result = std::panic::catch_unwind(|| {
+            shared.write(|buf| {
+                buf[0] = 0xff;
+                panic!("write failed");
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(shared.load(), [0x01; 8]);
+
+        shared.write(|buf| buf[0] = 0x02);
+        assert_eq!(shared.read(|buf| buf[0]), 0x02);
+    }
+
+    #[test]
+    fn no_tearing() {
+        let shared = SharedImage::<8>::new();
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                for i in 1..=10_000 {
+                    shared.write(|buf| {
+                        let mut pi = TestPiCounterMut::from(buf);
+                        *pi.low() = i;
+                        *pi.high() = i;
+                    });
+                }
+            });
+
+            for _ in 0..2 {
+                s.spawn(|| {
+                    let mut last = 0;
+                    while last < 10_000 {
+                        let (low, high) = shared.read(|buf| {
+                            let pi = TestPiCounter::from(buf);
+                            (pi.low(), pi.high())
+                        });
+                        assert_eq!(low, high);
+                        assert!(low >= last);
+                        last = low;
+                    }
+                });
+            }
+        });
+    }
+}
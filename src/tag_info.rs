@@ -0,0 +1,478 @@
+use core::ops::RangeInclusive;
+
+use crate::address::{AccessError, Address, Width};
+use crate::value::Value;
+
+/// Data type of a tag in a process image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DataType {
+    /// Boolean Bit (`X`)
+    Bool,
+    /// Byte (`B`)
+    Byte,
+    /// Word (`W`)
+    Word,
+    /// Double Word (`D`)
+    DWord,
+    /// Long Word (`L`)
+    LWord,
+    /// Short Integer (`SINT`)
+    SInt,
+    /// Integer (`INT`)
+    Int,
+    /// Double Integer (`DINT`)
+    DInt,
+    /// Long Integer (`LINT`)
+    LInt,
+    /// Real (`REAL`)
+    Real,
+    /// Long Real (`LREAL`)
+    LReal,
+}
+
+impl DataType {
+    /// The storage width of this data type in the process image.
+    pub const fn width(self) -> Width {
+        match self {
+            DataType::Bool => Width::Bit,
+            DataType::Byte | DataType::SInt => Width::Byte,
+            DataType::Word | DataType::Int => Width::Word,
+            DataType::DWord | DataType::DInt | DataType::Real => Width::DWord,
+            DataType::LWord | DataType::LInt | DataType::LReal => Width::LWord,
+        }
+    }
+}
+
+/// Metadata of a tag in a process image.
+///
+/// The [`TAGS`][`crate::ProcessImage::TAGS`] table of every process image contains one entry per
+/// tag, in the order of the definition:
+///
+/// ```
+/// use process_image::{DataType, ProcessImage, Value};
+///
+/// process_image::process_image! {
+///     pub struct PiDrive: 4 in Q {
+///         /// Enable the drive.
+///         pub enable: (X, 0, 0),
+///         /// Speed setpoint in rpm.
+///         pub speed: (INT, 2),
+///     }
+/// }
+///
+/// let tag = &PiDrive::TAGS[1];
+/// assert_eq!(tag.name(), "speed");
+/// assert_eq!(tag.address().to_string(), "%QW2");
+/// assert_eq!(tag.data_type(), DataType::Int);
+/// assert_eq!(tag.doc_lines().collect::<Vec<_>>(), ["Speed setpoint in rpm."]);
+///
+/// let pi = PiDrive::from(&[0x01, 0x00, 0xff, 0x38]);
+/// pi.visit_tags(|tag, value| println!("{} ({}) = {}", tag.name(), tag.address(), value));
+/// assert_eq!(tag.read(pi.as_bytes()), Value::Int(-200));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct TagInfo {
+    name: &'static str,
+    address: Address,
+    data_type: DataType,
+    doc: &'static [&'static str],
+    pub(crate) bit_field: Option<(u8, u8)>,
+    read: fn(&[u8]) -> Value,
+    write: fn(&mut [u8], Value) -> Result<(), AccessError>,
+}
+
+impl TagInfo {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        address: Address,
+        data_type: DataType,
+        doc: &'static [&'static str],
+        bit_field: Option<(u8, u8)>,
+        read: fn(&[u8]) -> Value,
+        write: fn(&mut [u8], Value) -> Result<(), AccessError>,
+    ) -> Self {
+        Self {
+            name,
+            address,
+            data_type,
+            doc,
+            bit_field,
+            read,
+            write,
+        }
+    }
+
+    /// Name of the tag.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Address of the tag in the process image.
+    pub const fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Data type of the tag.
+    pub const fn data_type(&self) -> DataType {
+        self.data_type
+    }
+
+    /// Index of the bit in the addressed byte or word, for bit tags.
+    pub const fn bit_index(&self) -> Option<u8> {
+        self.address.bit_offset()
+    }
+
+    /// Range of bits in the addressed byte or word, for bit field tags.
+    pub const fn bit_field(&self) -> Option<RangeInclusive<u8>> {
+        match self.bit_field {
+            Some((low, high)) => Some(RangeInclusive::new(low, high)),
+            None => None,
+        }
+    }
+
+    /// Lines of the doc comment of the tag.
+    pub fn doc_lines(&self) -> impl Iterator<Item = &'static str> {
+        self.doc
+            .iter()
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+    }
+
+    /// Read the value of the tag from the data of its process image.
+    ///
+    /// # Panics
+    /// Panics when `buf` is too small to contain the tag.
+    pub fn read(&self, buf: &[u8]) -> Value {
+        (self.read)(buf)
+    }
+
+    /// Write the value of the tag to the data of its process image.
+    ///
+    /// The value must be of the [data type][`TagInfo::data_type()`] of the tag, otherwise
+    /// [`AccessError::TypeMismatch`] is returned.
+    ///
+    /// # Panics
+    /// Panics when `buf` is too small to contain the tag.
+    pub fn write(&self, buf: &mut [u8], value: Value) -> Result<(), AccessError> {
+        (self.write)(buf, value)
+    }
+}
+
+/// Build the [`TAGS`][`crate::ProcessImage::TAGS`] table of a process image.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_table {
+    ($order:ty, $area:tt; $(
+        [$($attr:tt)*] $name:ident: ($($tag:tt)+)
+    ),*) => {
+        &[$(
+            $crate::TagInfo::new(
+                ::core::stringify!($name),
+                $crate::tag_address!($crate::area!($area), $($tag)+),
+                $crate::tag_data_type!($($tag)+),
+                $crate::tag_doc!([] $($attr)*),
+                $crate::tag_bit_field!($($tag)+),
+                |buf: &[u8]| $crate::tag_value!(buf, $order, $($tag)+),
+                |buf: &mut [u8], value: $crate::Value| {
+                    $crate::tag_store!(buf, value, $order, $($tag)+)
+                },
+            )
+        ),*]
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_data_type {
+    ($kind:ident @ $order:ident, $($addr:tt)+) => {
+        $crate::tag_data_type!($kind, $($addr)+)
+    };
+    ($kind:ident, $addr:literal, $low:literal ..= $high:literal) => {
+        $crate::tag_data_type!($kind, $addr)
+    };
+    (X, $addr1:literal, $addr2:literal) => {
+        $crate::DataType::Bool
+    };
+    ($kind:ident, $addr:literal, $bit:literal) => {
+        $crate::DataType::Bool
+    };
+    (B, $addr:literal) => {
+        $crate::DataType::Byte
+    };
+    (W, $addr:literal) => {
+        $crate::DataType::Word
+    };
+    (D, $addr:literal) => {
+        $crate::DataType::DWord
+    };
+    (L, $addr:literal) => {
+        $crate::DataType::LWord
+    };
+    (SINT, $addr:literal) => {
+        $crate::DataType::SInt
+    };
+    (INT, $addr:literal) => {
+        $crate::DataType::Int
+    };
+    (DINT, $addr:literal) => {
+        $crate::DataType::DInt
+    };
+    (LINT, $addr:literal) => {
+        $crate::DataType::LInt
+    };
+    (REAL, $addr:literal) => {
+        $crate::DataType::Real
+    };
+    (LREAL, $addr:literal) => {
+        $crate::DataType::LReal
+    };
+    ($addr1:literal, $addr2:literal) => {
+        $crate::DataType::Bool
+    };
+}
+
+/// Bits of a bit field tag.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_bit_field {
+    ($kind:ident @ $order:ident, $($addr:tt)+) => {
+        $crate::tag_bit_field!($kind, $($addr)+)
+    };
+    ($kind:ident, $addr:literal, $low:literal ..= $high:literal) => {
+        Some(($low, $high))
+    };
+    ($($tag:tt)+) => {
+        None
+    };
+}
+
+/// Rust type of the value of a tag.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_type {
+    (X) => {
+        bool
+    };
+    (B) => {
+        u8
+    };
+    (W) => {
+        u16
+    };
+    (D) => {
+        u32
+    };
+    (L) => {
+        u64
+    };
+    (SINT) => {
+        i8
+    };
+    (INT) => {
+        i16
+    };
+    (DINT) => {
+        i32
+    };
+    (LINT) => {
+        i64
+    };
+    (REAL) => {
+        f32
+    };
+    (LREAL) => {
+        f64
+    };
+}
+
+/// Collect the doc comments from the attributes of a tag.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_doc {
+    ([$($docs:literal)*]) => {
+        &[$($docs),*]
+    };
+    ([$($docs:literal)*] #[doc = $doc:literal] $($rest:tt)*) => {
+        $crate::tag_doc!([$($docs)* $doc] $($rest)*)
+    };
+    ([$($docs:literal)*] #[$($attr:tt)*] $($rest:tt)*) => {
+        $crate::tag_doc!([$($docs)*] $($rest)*)
+    };
+}
+
+/// Read the value of a tag from a buffer.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_value {
+    ($buf:ident, $order:ty, $kind:ident @ $tag_order:ident, $($addr:tt)+) => {
+        $crate::tag_value!($buf, $crate::byte_order!($tag_order), $kind, $($addr)+)
+    };
+    ($buf:ident, $order:ty, $kind:ident, $addr:literal, $low:literal ..= $high:literal) => {
+        $crate::Value::from($crate::BitField::bits(
+            <$crate::tag_type!($kind) as $crate::BitField>::read::<$order>(&$buf[$addr..]),
+            $low,
+            $high,
+        ))
+    };
+    ($buf:ident, $order:ty, X, $addr1:literal, $addr2:literal) => {
+        $crate::Value::Bool($buf[$addr1] & (1 << $addr2) != 0)
+    };
+    ($buf:ident, $order:ty, $kind:ident, $addr:literal, $bit:literal) => {{
+        let (byte, bit) =
+            <$crate::tag_type!($kind) as $crate::BitField>::bit_position::<$order>($bit);
+        $crate::Value::Bool($buf[$addr + byte] & (1 << bit) != 0)
+    }};
+    ($buf:ident, $order:ty, B, $addr:literal) => {
+        $crate::Value::Byte($buf[$addr])
+    };
+    ($buf:ident, $order:ty, W, $addr:literal) => {
+        $crate::Value::Word(<$order as $crate::ByteOrder>::read_u16(
+            $buf[$addr..$addr + 2].try_into().unwrap(),
+        ))
+    };
+    ($buf:ident, $order:ty, D, $addr:literal) => {
+        $crate::Value::DWord(<$order as $crate::ByteOrder>::read_u32(
+            $buf[$addr..$addr + 4].try_into().unwrap(),
+        ))
+    };
+    ($buf:ident, $order:ty, L, $addr:literal) => {
+        $crate::Value::LWord(<$order as $crate::ByteOrder>::read_u64(
+            $buf[$addr..$addr + 8].try_into().unwrap(),
+        ))
+    };
+    ($buf:ident, $order:ty, SINT, $addr:literal) => {
+        $crate::Value::SInt($buf[$addr] as i8)
+    };
+    ($buf:ident, $order:ty, INT, $addr:literal) => {
+        $crate::Value::Int(<$order as $crate::ByteOrder>::read_u16(
+            $buf[$addr..$addr + 2].try_into().unwrap(),
+        ) as i16)
+    };
+    ($buf:ident, $order:ty, DINT, $addr:literal) => {
+        $crate::Value::DInt(<$order as $crate::ByteOrder>::read_u32(
+            $buf[$addr..$addr + 4].try_into().unwrap(),
+        ) as i32)
+    };
+    ($buf:ident, $order:ty, LINT, $addr:literal) => {
+        $crate::Value::LInt(<$order as $crate::ByteOrder>::read_u64(
+            $buf[$addr..$addr + 8].try_into().unwrap(),
+        ) as i64)
+    };
+    ($buf:ident, $order:ty, REAL, $addr:literal) => {
+        $crate::Value::Real(f32::from_bits(<$order as $crate::ByteOrder>::read_u32(
+            $buf[$addr..$addr + 4].try_into().unwrap(),
+        )))
+    };
+    ($buf:ident, $order:ty, LREAL, $addr:literal) => {
+        $crate::Value::LReal(f64::from_bits(<$order as $crate::ByteOrder>::read_u64(
+            $buf[$addr..$addr + 8].try_into().unwrap(),
+        )))
+    };
+    ($buf:ident, $order:ty, $addr1:literal, $addr2:literal) => {
+        $crate::Value::Bool($buf[$addr1] & (1 << $addr2) != 0)
+    };
+}
+
+/// Write the value of a tag to a buffer.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! tag_store {
+    ($buf:ident, $value:ident, $order:ty, $kind:ident @ $tag_order:ident, $($addr:tt)+) => {
+        $crate::tag_store!($buf, $value, $crate::byte_order!($tag_order), $kind, $($addr)+)
+    };
+    (
+        $buf:ident, $value:ident, $order:ty,
+        $kind:ident, $addr:literal, $low:literal ..= $high:literal
+    ) => {
+        match <$crate::tag_type!($kind) as $crate::BitField>::from_value($value) {
+            Some(v) => {
+                let bytes = &mut $buf[$addr..];
+                let word = <$crate::tag_type!($kind) as $crate::BitField>::read::<$order>(bytes);
+                $crate::BitField::write::<$order>(
+                    $crate::BitField::with_bits(word, $low, $high, v),
+                    bytes,
+                );
+                Ok(())
+            }
+            None => Err($crate::AccessError::TypeMismatch),
+        }
+    };
+    ($buf:ident, $value:ident, $order:ty, X, $addr1:literal, $addr2:literal) => {
+        $crate::tag_store!($buf, $value, $order, $addr1, $addr2)
+    };
+    ($buf:ident, $value:ident, $order:ty, $kind:ident, $addr:literal, $bit:literal) => {{
+        let (byte, bit) =
+            <$crate::tag_type!($kind) as $crate::BitField>::bit_position::<$order>($bit);
+        $crate::tag_store!(@match $value, Bool, |v| {
+            $buf[$addr + byte] = ($buf[$addr + byte] & !(1 << bit)) | (u8::from(v) << bit)
+        })
+    }};
+    ($buf:ident, $value:ident, $order:ty, B, $addr:literal) => {
+        $crate::tag_store!(@match $value, Byte, |v| $buf[$addr] = v)
+    };
+    ($buf:ident, $value:ident, $order:ty, W, $addr:literal) => {
+        $crate::tag_store!(@match $value, Word, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u16(v);
+            $buf[$addr..$addr + 2].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, D, $addr:literal) => {
+        $crate::tag_store!(@match $value, DWord, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u32(v);
+            $buf[$addr..$addr + 4].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, L, $addr:literal) => {
+        $crate::tag_store!(@match $value, LWord, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u64(v);
+            $buf[$addr..$addr + 8].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, SINT, $addr:literal) => {
+        $crate::tag_store!(@match $value, SInt, |v| $buf[$addr] = v as u8)
+    };
+    ($buf:ident, $value:ident, $order:ty, INT, $addr:literal) => {
+        $crate::tag_store!(@match $value, Int, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u16(v as u16);
+            $buf[$addr..$addr + 2].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, DINT, $addr:literal) => {
+        $crate::tag_store!(@match $value, DInt, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u32(v as u32);
+            $buf[$addr..$addr + 4].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, LINT, $addr:literal) => {
+        $crate::tag_store!(@match $value, LInt, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u64(v as u64);
+            $buf[$addr..$addr + 8].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, REAL, $addr:literal) => {
+        $crate::tag_store!(@match $value, Real, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u32(v.to_bits());
+            $buf[$addr..$addr + 4].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, LREAL, $addr:literal) => {
+        $crate::tag_store!(@match $value, LReal, |v| {
+            let bytes = <$order as $crate::ByteOrder>::write_u64(v.to_bits());
+            $buf[$addr..$addr + 8].copy_from_slice(&bytes)
+        })
+    };
+    ($buf:ident, $value:ident, $order:ty, $addr1:literal, $addr2:literal) => {
+        $crate::tag_store!(@match $value, Bool, |v| {
+            $buf[$addr1] = ($buf[$addr1] & !(1 << $addr2)) | (u8::from(v) << $addr2)
+        })
+    };
+    (@match $value:ident, $variant:ident, |$v:ident| $store:expr) => {
+        match $value {
+            $crate::Value::$variant($v) => {
+                $store;
+                Ok(())
+            }
+            _ => Err($crate::AccessError::TypeMismatch),
+        }
+    };
+}
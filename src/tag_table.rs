@@ -0,0 +1,206 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt;
+use core::marker::PhantomData;
+
+use crate::address::{AccessError, Address};
+use crate::byte_order::{BigEndian, ByteOrder};
+use crate::value::Value;
+
+/// Tag table for symbolic access to a process image, built at runtime.
+///
+/// This is the runtime counterpart of [`process_image!{}`][`crate::process_image`], for tag lists
+/// which are only known at runtime, e.g. because they are loaded from a file.  All tags are
+/// checked to fit into the process image when the table is built.
+///
+/// Multi-byte tags are accessed in big-endian order by default.  Use
+/// [`TagTable::with_byte_order()`] for other byte orders.
+///
+/// # Example
+/// ```
+/// use process_image::{Address, TagTable, Value};
+///
+/// let tags = [
+///     ("door_closed", "%IX0.3".parse::<Address>().unwrap()),
+///     ("speed", "%IW2".parse().unwrap()),
+/// ];
+/// let table = TagTable::new(4, tags).unwrap();
+///
+/// let mut buf = [0x00; 4];
+/// table.write(&mut buf, "speed", Value::Word(1500)).unwrap();
+/// assert_eq!(table.read(&buf, "speed"), Ok(Value::Word(1500)));
+/// assert_eq!(table.read(&buf, "door_closed"), Ok(Value::Bool(false)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TagTable<O: ByteOrder = BigEndian> {
+    size: usize,
+    tags: BTreeMap<String, Address>,
+    order: PhantomData<O>,
+}
+
+impl TagTable<BigEndian> {
+    /// Build a tag table for a process image of `size` bytes.
+    pub fn new<I, S>(size: usize, tags: I) -> Result<Self, TagTableError>
+    where
+        I: IntoIterator<Item = (S, Address)>,
+        S: Into<String>,
+    {
+        Self::with_byte_order(size, tags)
+    }
+}
+
+impl<O: ByteOrder> TagTable<O> {
+    /// Build a tag table for a process image of `size` bytes with the byte order `O`.
+    pub fn with_byte_order<I, S>(size: usize, tags: I) -> Result<Self, TagTableError>
+    where
+        I: IntoIterator<Item = (S, Address)>,
+        S: Into<String>,
+    {
+        let mut table = BTreeMap::new();
+        for (name, address) in tags {
+            let name = name.into();
+            let end = address.byte_offset().checked_add(address.width().size());
+            if !matches!(end, Some(end) if end <= size) {
+                return Err(TagTableError::OutOfBounds(name));
+            }
+            if table.contains_key(&name) {
+                return Err(TagTableError::DuplicateTag(name));
+            }
+            table.insert(name, address);
+        }
+
+        Ok(Self {
+            size,
+            tags: table,
+            order: PhantomData,
+        })
+    }
+
+    /// Size of the process image in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Number of tags in the table.
+    pub fn len(&self) -> usize {
+        self.tags.len()
+    }
+
+    /// Whether the table contains no tags.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+    }
+
+    /// Address of the tag `name`.
+    pub fn get(&self, name: &str) -> Option<Address> {
+        self.tags.get(name).copied()
+    }
+
+    /// Iterate over all tags, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Address)> {
+        self.tags
+            .iter()
+            .map(|(name, address)| (name.as_str(), *address))
+    }
+
+    /// Read the value of the tag `name` from a process image buffer.
+    pub fn read(&self, buf: &[u8], name: &str) -> Result<Value, AccessError> {
+        self.get(name)
+            .ok_or(AccessError::UnknownTag)?
+            .read_with_byte_order::<O>(buf)
+    }
+
+    /// Write the value of the tag `name` to a process image buffer.
+    ///
+    /// The value must match the width of the tag's address, see [`Address::write()`].
+    pub fn write(&self, buf: &mut [u8], name: &str, value: Value) -> Result<(), AccessError> {
+        self.get(name)
+            .ok_or(AccessError::UnknownTag)?
+            .write_with_byte_order::<O>(buf, value)
+    }
+}
+
+/// Error when building a [`TagTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagTableError {
+    /// The tag with this name does not fit into the process image.
+    OutOfBounds(String),
+    /// A tag with this name is already in the table.
+    DuplicateTag(String),
+}
+
+impl fmt::Display for TagTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TagTableError::OutOfBounds(name) => {
+                write!(f, "tag `{}` exceeds the size of the process image", name)
+            }
+            TagTableError::DuplicateTag(name) => write!(f, "duplicate tag `{}`", name),
+        }
+    }
+}
+
+impl core::error::Error for TagTableError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LittleEndian;
+
+    fn tags() -> [(&'static str, Address); 3] {
+        [
+            ("enable", "%QX0.0".parse().unwrap()),
+            ("mode", "%QB1".parse().unwrap()),
+            ("speed", "%QW2".parse().unwrap()),
+        ]
+    }
+
+    #[test]
+    fn read_write() {
+        let table = TagTable::new(4, tags()).unwrap();
+        assert_eq!(table.size(), 4);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.get("mode"), Some("%QB1".parse().unwrap()));
+
+        let mut buf = [0x00; 4];
+        table.write(&mut buf, "enable", Value::Bool(true)).unwrap();
+        table.write(&mut buf, "speed", Value::Int(-2)).unwrap();
+        assert_eq!(buf, [0x01, 0x00, 0xff, 0xfe]);
+        assert_eq!(table.read(&buf, "speed"), Ok(Value::Word(0xfffe)));
+
+        assert_eq!(table.read(&buf, "unknown"), Err(AccessError::UnknownTag));
+        assert_eq!(
+            table.write(&mut buf, "mode", Value::Word(1)),
+            Err(AccessError::TypeMismatch)
+        );
+        assert_eq!(
+            table.read(&buf[..2], "speed"),
+            Err(AccessError::OutOfBounds)
+        );
+
+        let table = TagTable::<LittleEndian>::with_byte_order(4, tags()).unwrap();
+        assert_eq!(table.read(&buf, "speed"), Ok(Value::Word(0xfeff)));
+
+        let names: alloc::vec::Vec<_> = table.iter().map(|(name, _)| name).collect();
+        assert_eq!(names, ["enable", "mode", "speed"]);
+    }
+
+    #[test]
+    fn validation() {
+        assert_eq!(
+            TagTable::new(3, tags()).unwrap_err(),
+            TagTableError::OutOfBounds("speed".into())
+        );
+        assert_eq!(
+            TagTable::new(4, [("huge", "%MW18446744073709551615".parse().unwrap())]).unwrap_err(),
+            TagTableError::OutOfBounds("huge".into())
+        );
+
+        let mut tags = tags();
+        tags[2].0 = "mode";
+        assert_eq!(
+            TagTable::new(4, tags).unwrap_err(),
+            TagTableError::DuplicateTag("mode".into())
+        );
+    }
+}
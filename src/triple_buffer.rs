@@ -0,0 +1,232 @@
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use crate::image::ProcessImageMut;
+
+/// Index of the shared buffer in the state.
+const INDEX: u8 = 0b011;
+/// Set when the shared buffer holds an image the consumer has not seen yet.
+const UPDATED: u8 = 0b100;
+
+/// Lock-free triple buffer for handing owned process images from one thread to another.
+///
+/// The producer (e.g. the I/O thread) fills its private image and publishes it as a whole.  The
+/// consumer (e.g. the control loop) always gets a consistent snapshot of the last published image,
+/// no tag can ever be torn between two cycles.  Neither side ever waits for the other.
+///
+/// The buffer is split into a [`Producer`] and a [`Consumer`] half which can be moved to
+/// different threads.  It works in `no_std` and needs no allocator, only atomic operations on
+/// bytes.
+///
+/// # Example
+/// ```
+/// use process_image::TripleBuffer;
+///
+/// process_image::process_image_owned! {
+///     pub struct PiInputs, mut PiInputsMut: 4 {
+///         pub door_closed: (X, 0, 3),
+///         pub speed: (W, 2),
+///     }
+/// }
+///
+/// let mut buffer = TripleBuffer::new(PiInputs::new_zeroed());
+/// let (mut producer, mut consumer) = buffer.split();
+///
+/// std::thread::scope(|s| {
+///     s.spawn(move || {
+///         // I/O thread: Receive the inputs and publish them.
+///         let pi = producer.image_mut();
+///         pi.as_slice_mut().copy_from_slice(&[0x08, 0x00, 0x05, 0xdc]);
+///         producer.publish();
+///     })
+///     .join()
+///     .unwrap();
+///
+///     // Control loop: Work on a snapshot of the latest inputs.
+///     let pi = consumer.snapshot();
+///     assert!(pi.door_closed());
+///     assert_eq!(pi.speed(), 1500);
+/// });
+/// ```
+pub struct TripleBuffer<P> {
+    buffers: [UnsafeCell<P>; 3],
+    shared: AtomicU8,
+    write: u8,
+    read: u8,
+}
+
+impl<P> TripleBuffer<P>
+where
+    P: ProcessImageMut + for<'a> TryFrom<&'a [u8]>,
+{
+    /// Create a triple buffer where all three buffers contain a copy of `initial`.
+    pub fn new(initial: P) -> Self {
+        let copy = || match P::try_from(initial.as_bytes()) {
+            Ok(pi) => pi,
+            Err(_) => unreachable!("process image has a fixed size"),
+        };
+        Self {
+            buffers: [
+                UnsafeCell::new(copy()),
+                UnsafeCell::new(copy()),
+                UnsafeCell::new(initial),
+            ],
+            shared: AtomicU8::new(1),
+            write: 0,
+            read: 2,
+        }
+    }
+}
+
+impl<P> TripleBuffer<P> {
+    /// Split the buffer into its producer and consumer half.
+    pub fn split(&mut self) -> (Producer<'_, P>, Consumer<'_, P>) {
+        let TripleBuffer {
+            buffers,
+            shared,
+            write,
+            read,
+        } = self;
+        (
+            Producer {
+                buffers,
+                shared,
+                index: write,
+            },
+            Consumer {
+                buffers,
+                shared,
+                index: read,
+            },
+        )
+    }
+}
+
+impl<P: core::fmt::Debug> core::fmt::Debug for TripleBuffer<P> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // SAFETY: Both halves borrow the buffer mutably, so none of them exists right now.
+        let snapshot = unsafe { &*self.buffers[usize::from(self.read)].get() };
+        f.debug_struct("TripleBuffer")
+            .field("snapshot", snapshot)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Writing half of a [`TripleBuffer`].
+pub struct Producer<'a, P> {
+    buffers: &'a [UnsafeCell<P>; 3],
+    shared: &'a AtomicU8,
+    index: &'a mut u8,
+}
+
+// SAFETY: The producer only accesses the buffer it owns.
+unsafe impl<P: Send> Send for Producer<'_, P> {}
+
+impl<P> Producer<'_, P> {
+    /// The process image which is published next.
+    ///
+    /// After [`publish()`][`Producer::publish()`], this is an older image again, so it should be
+    /// written completely in every cycle.
+    pub fn image_mut(&mut self) -> &mut P {
+        // SAFETY: The buffer at `index` is owned by the producer.
+        unsafe { &mut *self.buffers[usize::from(*self.index)].get() }
+    }
+
+    /// Publish the process image, the consumer sees it with its next snapshot.
+    pub fn publish(&mut self) {
+        let previous = self.shared.swap(*self.index | UPDATED, Ordering::AcqRel);
+        *self.index = previous & INDEX;
+    }
+}
+
+/// Reading half of a [`TripleBuffer`].
+pub struct Consumer<'a, P> {
+    buffers: &'a [UnsafeCell<P>; 3],
+    shared: &'a AtomicU8,
+    index: &'a mut u8,
+}
+
+// SAFETY: The consumer only accesses the buffer it owns.
+unsafe impl<P: Send> Send for Consumer<'_, P> {}
+
+impl<P> Consumer<'_, P> {
+    /// Whether a new process image was published since the last snapshot.
+    pub fn has_update(&self) -> bool {
+        self.shared.load(Ordering::Relaxed) & UPDATED != 0
+    }
+
+    /// Snapshot of the latest published process image.
+    ///
+    /// The snapshot does not change until the next call, even when the producer publishes new
+    /// images in the meantime.
+    pub fn snapshot(&mut self) -> &P {
+        if self.has_update() {
+            let previous = self.shared.swap(*self.index, Ordering::AcqRel);
+            *self.index = previous & INDEX;
+        }
+        // SAFETY: The buffer at `index` is owned by the consumer.
+        unsafe { &*self.buffers[usize::from(*self.index)].get() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::process_image_owned! {
+        pub struct TestPiCounter, mut TestPiCounterMut: 8 {
+            pub low: (D, 0),
+            pub high: (D, 4),
+        }
+    }
+
+    #[test]
+    fn publish_and_snapshot() {
+        let mut buffer = TripleBuffer::new(TestPiCounter::from(&[0x00, 0, 0, 1, 0, 0, 0, 1]));
+        let (mut producer, mut consumer) = buffer.split();
+        assert!(!consumer.has_update());
+        assert_eq!(consumer.snapshot().low(), 1);
+
+        *producer.image_mut().as_mut().low() = 2;
+        producer.publish();
+        *producer.image_mut().as_mut().low() = 3;
+        assert!(consumer.has_update());
+        assert_eq!(consumer.snapshot().low(), 2);
+        assert_eq!(consumer.snapshot().high(), 1);
+
+        producer.publish();
+        *producer.image_mut().as_mut().low() = 4;
+        producer.publish();
+        assert_eq!(consumer.snapshot().low(), 4);
+        assert!(!consumer.has_update());
+
+        // Splitting again continues with the same buffers.
+        let (_, mut consumer) = buffer.split();
+        assert_eq!(consumer.snapshot().low(), 4);
+    }
+
+    #[test]
+    fn no_tearing() {
+        let mut buffer = TripleBuffer::new(TestPiCounter::new_zeroed());
+        let (mut producer, mut consumer) = buffer.split();
+
+        std::thread::scope(|s| {
+            s.spawn(move || {
+                for i in 1..=10_000 {
+                    let mut pi = producer.image_mut().as_mut();
+                    *pi.low() = i;
+                    *pi.high() = i;
+                    producer.publish();
+                }
+            });
+
+            let mut last = 0;
+            while last < 10_000 {
+                let pi = consumer.snapshot();
+                assert_eq!(pi.low(), pi.high());
+                assert!(pi.low() >= last);
+                last = pi.low();
+            }
+        });
+    }
+}
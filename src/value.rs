@@ -0,0 +1,123 @@
+use core::fmt;
+
+/// A typed value read from or written to a process image at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// Boolean Bit (`X`)
+    Bool(bool),
+    /// Byte (`B`)
+    Byte(u8),
+    /// Word (`W`)
+    Word(u16),
+    /// Double Word (`D`)
+    DWord(u32),
+    /// Long Word (`L`)
+    LWord(u64),
+    /// Short Integer (`SINT`)
+    SInt(i8),
+    /// Integer (`INT`)
+    Int(i16),
+    /// Double Integer (`DINT`)
+    DInt(i32),
+    /// Long Integer (`LINT`)
+    LInt(i64),
+    /// Real (`REAL`)
+    Real(f32),
+    /// Long Real (`LREAL`)
+    LReal(f64),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(v) => v.fmt(f),
+            Value::Byte(v) => v.fmt(f),
+            Value::Word(v) => v.fmt(f),
+            Value::DWord(v) => v.fmt(f),
+            Value::LWord(v) => v.fmt(f),
+            Value::SInt(v) => v.fmt(f),
+            Value::Int(v) => v.fmt(f),
+            Value::DInt(v) => v.fmt(f),
+            Value::LInt(v) => v.fmt(f),
+            Value::Real(v) => v.fmt(f),
+            Value::LReal(v) => v.fmt(f),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    #[inline(always)]
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<u8> for Value {
+    #[inline(always)]
+    fn from(value: u8) -> Self {
+        Value::Byte(value)
+    }
+}
+
+impl From<u16> for Value {
+    #[inline(always)]
+    fn from(value: u16) -> Self {
+        Value::Word(value)
+    }
+}
+
+impl From<u32> for Value {
+    #[inline(always)]
+    fn from(value: u32) -> Self {
+        Value::DWord(value)
+    }
+}
+
+impl From<u64> for Value {
+    #[inline(always)]
+    fn from(value: u64) -> Self {
+        Value::LWord(value)
+    }
+}
+
+impl From<i8> for Value {
+    #[inline(always)]
+    fn from(value: i8) -> Self {
+        Value::SInt(value)
+    }
+}
+
+impl From<i16> for Value {
+    #[inline(always)]
+    fn from(value: i16) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<i32> for Value {
+    #[inline(always)]
+    fn from(value: i32) -> Self {
+        Value::DInt(value)
+    }
+}
+
+impl From<i64> for Value {
+    #[inline(always)]
+    fn from(value: i64) -> Self {
+        Value::LInt(value)
+    }
+}
+
+impl From<f32> for Value {
+    #[inline(always)]
+    fn from(value: f32) -> Self {
+        Value::Real(value)
+    }
+}
+
+impl From<f64> for Value {
+    #[inline(always)]
+    fn from(value: f64) -> Self {
+        Value::LReal(value)
+    }
+}
@@ -0,0 +1,462 @@
+use core::marker::PhantomData;
+use core::ops::Deref;
+use core::ops::DerefMut;
+use core::ptr::NonNull;
+
+use crate::access::BitField;
+use crate::byte_order::{BigEndian, ByteOrder};
+
+/// Process image of `N` bytes in memory-mapped I/O, accessed only with volatile reads and writes.
+///
+/// This is a raw pointer wrapper for process images which are shared with hardware, e.g. the
+/// dual-ported RAM of a fieldbus ASIC.  Every access goes to memory, the compiler can neither
+/// merge nor elide it.  Words, double words, and long words which are aligned in memory are
+/// accessed with a single volatile access of their full width, unaligned ones byte by byte.
+///
+/// This is the buffer of the volatile structs generated by
+/// [`process_image!{}`][`crate::process_image`].
+#[derive(Debug)]
+pub struct VolatileBuffer<'a, const N: usize> {
+    ptr: NonNull<u8>,
+    buf: PhantomData<&'a mut [u8; N]>,
+}
+
+impl<'a, const N: usize> VolatileBuffer<'a, N> {
+    /// Create a buffer for the `N` bytes at `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must be non-null and valid for volatile reads and writes of `N` bytes for the whole
+    /// lifetime `'a`.
+    #[inline(always)]
+    pub const unsafe fn from_ptr(ptr: *mut u8) -> Self {
+        Self {
+            // SAFETY: Guaranteed by the caller.
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            buf: PhantomData,
+        }
+    }
+
+    /// Pointer to the first byte of the buffer.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+
+    /// Copy of the buffer contents, read byte by byte.
+    pub fn load(&self) -> [u8; N] {
+        let mut copy = [0x00; N];
+        for (i, byte) in copy.iter_mut().enumerate() {
+            // SAFETY: `i` is in bounds of the buffer.
+            *byte = unsafe { self.ptr.as_ptr().add(i).read_volatile() };
+        }
+        copy
+    }
+
+    /// Overwrite the buffer contents, written byte by byte.
+    pub fn store(&mut self, buf: &[u8; N]) {
+        for (i, byte) in buf.iter().enumerate() {
+            // SAFETY: `i` is in bounds of the buffer.
+            unsafe { self.ptr.as_ptr().add(i).write_volatile(*byte) };
+        }
+    }
+
+    /// Read the value at byte offset `offset` in byte order `O`.
+    ///
+    /// # Panics
+    /// Panics when the value exceeds the buffer.
+    #[inline(always)]
+    pub fn read<T: VolatileValue, O: ByteOrder>(&self, offset: usize) -> T {
+        // SAFETY: The value is in bounds of the buffer.
+        unsafe { T::read::<O>(self.offset::<T>(offset)) }
+    }
+
+    /// Write `value` at byte offset `offset` in byte order `O`.
+    ///
+    /// # Panics
+    /// Panics when the value exceeds the buffer.
+    #[inline(always)]
+    pub fn write<T: VolatileValue, O: ByteOrder>(&mut self, offset: usize, value: T) {
+        // SAFETY: The value is in bounds of the buffer.
+        unsafe { T::write::<O>(self.offset::<T>(offset), value) }
+    }
+
+    /// Read bit `index` of the byte at `offset`.
+    #[inline(always)]
+    pub fn bit(&self, offset: usize, index: u8) -> bool {
+        self.read::<u8, BigEndian>(offset) & (1 << index) != 0
+    }
+
+    /// Mutable accessor for bit `index` of the byte at `offset`.
+    #[inline(always)]
+    pub fn bit_mut(&mut self, offset: usize, index: u8) -> VolatileBitMut<'_> {
+        VolatileBitMut::new(self.offset::<u8>(offset), index)
+    }
+
+    /// Mutable accessor for the bits `low..=high` of the value at byte offset `offset` in byte
+    /// order `O`.
+    #[inline(always)]
+    pub fn bit_field_mut<T: VolatileValue + BitField, O: ByteOrder>(
+        &mut self,
+        offset: usize,
+        low: u8,
+        high: u8,
+    ) -> VolatileBitFieldMut<'_, T, O> {
+        VolatileBitFieldMut::new(self.offset::<T>(offset), low, high)
+    }
+
+    /// Mutable accessor for the value at byte offset `offset` in byte order `O`.
+    #[inline(always)]
+    pub fn get_mut<T: VolatileValue, O: ByteOrder>(
+        &mut self,
+        offset: usize,
+    ) -> VolatileMut<'_, T, O> {
+        VolatileMut::new(self.offset::<T>(offset))
+    }
+
+    #[inline(always)]
+    fn offset<T>(&self, offset: usize) -> *mut u8 {
+        assert!(
+            offset + core::mem::size_of::<T>() <= N,
+            "tag exceeds the process image"
+        );
+        // SAFETY: The offset is in bounds of the buffer.
+        unsafe { self.ptr.as_ptr().add(offset) }
+    }
+}
+
+impl<'a, const N: usize> From<&'a mut [u8; N]> for VolatileBuffer<'a, N> {
+    #[inline(always)]
+    fn from(buf: &'a mut [u8; N]) -> Self {
+        // SAFETY: The buffer is borrowed mutably for `'a`.
+        unsafe { Self::from_ptr(buf.as_mut_ptr()) }
+    }
+}
+
+/// Value type of a tag in a [`VolatileBuffer`].
+///
+/// This is implemented for all integer and floating point types of the tags.
+pub trait VolatileValue: Copy {
+    #[doc(hidden)]
+    unsafe fn read<O: ByteOrder>(ptr: *const u8) -> Self;
+
+    #[doc(hidden)]
+    unsafe fn write<O: ByteOrder>(ptr: *mut u8, value: Self);
+}
+
+impl VolatileValue for u8 {
+    #[inline(always)]
+    unsafe fn read<O: ByteOrder>(ptr: *const u8) -> Self {
+        unsafe { ptr.read_volatile() }
+    }
+
+    #[inline(always)]
+    unsafe fn write<O: ByteOrder>(ptr: *mut u8, value: Self) {
+        unsafe { ptr.write_volatile(value) }
+    }
+}
+
+impl VolatileValue for i8 {
+    #[inline(always)]
+    unsafe fn read<O: ByteOrder>(ptr: *const u8) -> Self {
+        unsafe { ptr.read_volatile() as i8 }
+    }
+
+    #[inline(always)]
+    unsafe fn write<O: ByteOrder>(ptr: *mut u8, value: Self) {
+        unsafe { ptr.write_volatile(value as u8) }
+    }
+}
+
+macro_rules! volatile_value {
+    ($($ty:ident: $int:ident, $read:ident, $write:ident, |$v:ident| $from:expr, $into:expr;)*) => {
+        $(
+            impl VolatileValue for $ty {
+                #[inline(always)]
+                unsafe fn read<O: ByteOrder>(ptr: *const u8) -> Self {
+                    let $v = O::$read(unsafe { read_bytes::<$int>(ptr) });
+                    $from
+                }
+
+                #[inline(always)]
+                unsafe fn write<O: ByteOrder>(ptr: *mut u8, value: Self) {
+                    let $v = value;
+                    unsafe { write_bytes::<$int>(ptr, O::$write($into)) }
+                }
+            }
+        )*
+    };
+}
+
+volatile_value! {
+    u16: u16, read_u16, write_u16, |v| v, v;
+    u32: u32, read_u32, write_u32, |v| v, v;
+    u64: u64, read_u64, write_u64, |v| v, v;
+    i16: u16, read_u16, write_u16, |v| v as i16, v as u16;
+    i32: u32, read_u32, write_u32, |v| v as i32, v as u32;
+    i64: u64, read_u64, write_u64, |v| v as i64, v as u64;
+    f32: u32, read_u32, write_u32, |v| f32::from_bits(v), v.to_bits();
+    f64: u64, read_u64, write_u64, |v| f64::from_bits(v), v.to_bits();
+}
+
+/// Integer types used for a single volatile access of a whole word.
+trait Word: Copy {
+    type Bytes: AsRef<[u8]> + AsMut<[u8]> + Default;
+
+    fn from_ne_bytes(bytes: Self::Bytes) -> Self;
+    fn to_ne_bytes(self) -> Self::Bytes;
+}
+
+macro_rules! word {
+    ($($int:ident: $size:literal),*) => {
+        $(
+            impl Word for $int {
+                type Bytes = [u8; $size];
+
+                #[inline(always)]
+                fn from_ne_bytes(bytes: Self::Bytes) -> Self {
+                    $int::from_ne_bytes(bytes)
+                }
+
+                #[inline(always)]
+                fn to_ne_bytes(self) -> Self::Bytes {
+                    $int::to_ne_bytes(self)
+                }
+            }
+        )*
+    };
+}
+
+word!(u16: 2, u32: 4, u64: 8);
+
+/// Read a word at `ptr`, with a single volatile access if it is aligned.
+#[inline(always)]
+unsafe fn read_bytes<W: Word>(ptr: *const u8) -> W::Bytes {
+    let word = ptr.cast::<W>();
+    if word.is_aligned() {
+        return unsafe { word.read_volatile() }.to_ne_bytes();
+    }
+    let mut bytes = W::Bytes::default();
+    for (i, byte) in bytes.as_mut().iter_mut().enumerate() {
+        *byte = unsafe { ptr.add(i).read_volatile() };
+    }
+    bytes
+}
+
+/// Write a word at `ptr`, with a single volatile access if it is aligned.
+#[inline(always)]
+unsafe fn write_bytes<W: Word>(ptr: *mut u8, bytes: W::Bytes) {
+    let word = ptr.cast::<W>();
+    if word.is_aligned() {
+        return unsafe { word.write_volatile(W::from_ne_bytes(bytes)) };
+    }
+    for (i, byte) in bytes.as_ref().iter().enumerate() {
+        unsafe { ptr.add(i).write_volatile(*byte) };
+    }
+}
+
+/// Mutable accessor for a single bit in a [`VolatileBuffer`].
+///
+/// Like [`BitMut`][`crate::BitMut`], this type dereferences to an `&mut bool`.  The byte is read
+/// with a volatile read when the accessor is created and the bit is written back with a volatile
+/// read-modify-write of the byte when it is dropped.
+#[derive(Debug)]
+pub struct VolatileBitMut<'a> {
+    ptr: *mut u8,
+    index: u8,
+    value: bool,
+    buf: PhantomData<&'a mut u8>,
+}
+
+impl VolatileBitMut<'_> {
+    #[inline(always)]
+    fn new(ptr: *mut u8, index: u8) -> Self {
+        // SAFETY: The pointer is in bounds of the buffer.
+        let value = unsafe { ptr.read_volatile() } & (1 << index) != 0;
+        Self {
+            ptr,
+            index,
+            value,
+            buf: PhantomData,
+        }
+    }
+}
+
+impl Deref for VolatileBitMut<'_> {
+    type Target = bool;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for VolatileBitMut<'_> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl Drop for VolatileBitMut<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: The pointer is in bounds of the buffer.
+        unsafe {
+            let byte = self.ptr.read_volatile() & !(1 << self.index);
+            self.ptr
+                .write_volatile(byte | u8::from(self.value) << self.index);
+        }
+    }
+}
+
+/// Mutable accessor for a value in a [`VolatileBuffer`].
+///
+/// Like [`WordMut`][`crate::WordMut`], this type dereferences to an `&mut T`.  The value is read
+/// with a volatile read when the accessor is created and written back with a volatile write when
+/// it is dropped.  The byte order is selected by the type parameter `O`.
+#[derive(Debug)]
+pub struct VolatileMut<'a, T: VolatileValue, O: ByteOrder = BigEndian> {
+    ptr: *mut u8,
+    value: T,
+    buf: PhantomData<&'a mut T>,
+    order: PhantomData<O>,
+}
+
+impl<T: VolatileValue, O: ByteOrder> VolatileMut<'_, T, O> {
+    #[inline(always)]
+    fn new(ptr: *mut u8) -> Self {
+        // SAFETY: The value is in bounds of the buffer.
+        let value = unsafe { T::read::<O>(ptr) };
+        Self {
+            ptr,
+            value,
+            buf: PhantomData,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: VolatileValue, O: ByteOrder> Deref for VolatileMut<'_, T, O> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: VolatileValue, O: ByteOrder> DerefMut for VolatileMut<'_, T, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T: VolatileValue, O: ByteOrder> Drop for VolatileMut<'_, T, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: The value is in bounds of the buffer.
+        unsafe { T::write::<O>(self.ptr, self.value) }
+    }
+}
+
+/// Mutable accessor for a bit field in a [`VolatileBuffer`].
+///
+/// Like [`BitFieldMut`][`crate::BitFieldMut`], this type dereferences to an `&mut T`.  The value
+/// is read with a volatile read when the accessor is created and only the bits of the field are
+/// written back with a volatile read-modify-write when it is dropped.  The byte order is selected
+/// by the type parameter `O`.
+#[derive(Debug)]
+pub struct VolatileBitFieldMut<'a, T: VolatileValue + BitField, O: ByteOrder = BigEndian> {
+    ptr: *mut u8,
+    low: u8,
+    high: u8,
+    value: T,
+    buf: PhantomData<&'a mut T>,
+    order: PhantomData<O>,
+}
+
+impl<T: VolatileValue + BitField, O: ByteOrder> VolatileBitFieldMut<'_, T, O> {
+    #[inline(always)]
+    fn new(ptr: *mut u8, low: u8, high: u8) -> Self {
+        // SAFETY: The value is in bounds of the buffer.
+        let value = unsafe { <T as VolatileValue>::read::<O>(ptr) }.bits(low, high);
+        Self {
+            ptr,
+            low,
+            high,
+            value,
+            buf: PhantomData,
+            order: PhantomData,
+        }
+    }
+}
+
+impl<T: VolatileValue + BitField, O: ByteOrder> Deref for VolatileBitFieldMut<'_, T, O> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T: VolatileValue + BitField, O: ByteOrder> DerefMut for VolatileBitFieldMut<'_, T, O> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+impl<T: VolatileValue + BitField, O: ByteOrder> Drop for VolatileBitFieldMut<'_, T, O> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // SAFETY: The value is in bounds of the buffer.
+        unsafe {
+            let word = <T as VolatileValue>::read::<O>(self.ptr);
+            let word = word.with_bits(self.low, self.high, self.value);
+            <T as VolatileValue>::write::<O>(self.ptr, word);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LittleEndian;
+
+    #[test]
+    fn read_write() {
+        let mut mem = [0x00u8; 12];
+        let mut buf = VolatileBuffer::from(&mut mem);
+
+        buf.write::<u16, BigEndian>(0, 0x1234);
+        buf.write::<i32, LittleEndian>(4, -2);
+        *buf.get_mut::<f32, BigEndian>(8) = 1.5;
+        *buf.bit_mut(3, 7) = true;
+        assert_eq!(buf.read::<u16, BigEndian>(0), 0x1234);
+        assert_eq!(buf.read::<i32, LittleEndian>(4), -2);
+        assert_eq!(buf.read::<f32, BigEndian>(8), 1.5);
+        assert!(buf.bit(3, 7));
+        assert!(!buf.bit(3, 6));
+
+        // Unaligned words are accessed byte by byte.
+        buf.write::<u16, BigEndian>(1, 0xabcd);
+        assert_eq!(buf.read::<u16, BigEndian>(1), 0xabcd);
+
+        assert_eq!(
+            buf.load(),
+            [0x12, 0xab, 0xcd, 0x80, 0xfe, 0xff, 0xff, 0xff, 0x3f, 0xc0, 0x00, 0x00]
+        );
+        buf.store(&[0x00; 12]);
+        assert_eq!(mem, [0x00; 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "tag exceeds the process image")]
+    fn out_of_bounds() {
+        let mut mem = [0x00u8; 4];
+        let buf = VolatileBuffer::from(&mut mem);
+        buf.read::<u32, BigEndian>(2);
+    }
+}